@@ -3,25 +3,18 @@ use enum_iterator::all;
 
 use crate::card::{Card, CardSuit};
 
+pub(super) const JOKER_PATH: &str = "cards/JK.png";
+
 impl From<&Card> for AssetPath<'_> {
     fn from(card: &Card) -> Self {
-        let s = match card.suit() {
-            CardSuit::Hearts => "H",
-            CardSuit::Diamonds => "D",
-            CardSuit::Clubs => "C",
-            CardSuit::Spades => "S",
-        };
-
-        let r = match card.rank() {
-            1 => "A".to_string(),
-            11 => "J".to_string(),
-            12 => "Q".to_string(),
-            13 => "K".to_string(),
-            2..=10 => card.rank().to_string(),
-            _ => unreachable!(),
-        };
-
-        AssetPath::from(format!("cards/{}{}.png", s, r))
+        if let Some(path) = card.definition().and_then(|d| d.art.path()) {
+            return path.clone();
+        }
+        if card.is_joker() {
+            // every joker shares the same art
+            return AssetPath::from(JOKER_PATH);
+        }
+        AssetPath::from(format!("cards/{}.png", card))
     }
 }
 
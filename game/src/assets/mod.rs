@@ -2,7 +2,7 @@ mod card;
 mod card_slot;
 
 use bevy::prelude::*;
-use card::all_cards;
+use card::{JOKER_PATH, all_cards};
 use card_slot::all_card_slots;
 
 use crate::{card::Card, card_slot::CardSlotSprite};
@@ -10,18 +10,24 @@ use crate::{card::Card, card_slot::CardSlotSprite};
 #[derive(Resource)]
 pub struct AssetHandles {
     card_fronts: [[Handle<Image>; 4]; 13],
+    card_joker: Handle<Image>,
     card_slots: [Handle<Image>; 3],
+    padlock: Handle<Image>,
 }
 
 impl AssetHandles {
     pub fn load(server: &AssetServer) -> Self {
         let mut new = AssetHandles {
             card_fronts: Default::default(),
+            card_joker: server.load(JOKER_PATH),
             card_slots: Default::default(),
+            padlock: server.load("icons/padlock.png"),
         };
 
         for card in all_cards() {
-            new.card_fronts[(card.rank() - 1) as usize][card.suit() as usize] = server.load(&card);
+            if let (Some(suit), Some(rank)) = (card.suit(), card.rank()) {
+                new.card_fronts[(rank - 1) as usize][suit as usize] = server.load(&card);
+            }
         }
 
         for card_slot in all_card_slots() {
@@ -32,10 +38,22 @@ impl AssetHandles {
     }
 
     pub fn get_card_image(&self, card: &Card) -> Handle<Image> {
-        self.card_fronts[(card.rank() - 1) as usize][card.suit() as usize].clone_weak()
+        if let Some(definition) = card.definition() {
+            return definition.art.clone_weak();
+        }
+        match (card.suit(), card.rank()) {
+            (Some(suit), Some(rank)) => {
+                self.card_fronts[(rank - 1) as usize][suit as usize].clone_weak()
+            }
+            _ => self.card_joker.clone_weak(),
+        }
     }
 
     pub fn get_card_slot_image(&self, card_slot: CardSlotSprite) -> Handle<Image> {
         self.card_slots[card_slot as usize].clone_weak()
     }
+
+    pub fn get_padlock_image(&self) -> Handle<Image> {
+        self.padlock.clone_weak()
+    }
 }
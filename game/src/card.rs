@@ -1,5 +1,10 @@
+use std::{fmt, str::FromStr, sync::Arc};
+
 use bevy::ecs::component::Component;
-use enum_iterator::Sequence;
+use enum_iterator::{Sequence, all};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+use crate::card_definition::CardDefinition;
 
 #[derive(Clone, Copy, Sequence, PartialEq, Eq)]
 #[repr(usize)]
@@ -10,23 +15,39 @@ pub enum CardSuit {
     Spades,
 }
 
-#[derive(Clone, Component)]
-#[component(immutable)]
-pub struct Card {
-    suit: CardSuit,
-    rank: u32,
+/// what a joker does when played, see [`crate::rules::Rules::jokers`]
+#[derive(Clone, Copy, Sequence, PartialEq, Eq, Debug)]
+pub enum JokerEffect {
+    /// discards every other card of the room
+    ClearRoom,
+    /// the next potion heals twice its value
+    DoublePotion,
+    /// fights as a monster that every weapon can take on
+    AnyMonster,
+}
+
+#[derive(Clone, PartialEq, Eq)]
+enum CardKind {
+    Suited { suit: CardSuit, rank: u32 },
+    Joker(JokerEffect),
+    Custom(Arc<CardDefinition>),
 }
 
+#[derive(Clone, Component, PartialEq, Eq)]
+#[component(immutable)]
+pub struct Card(CardKind);
+
 #[derive(Debug)]
 pub struct InvalideCardError;
 
 impl Card {
     #[inline]
     pub const fn new(suit: CardSuit, rank: u32) -> Self {
-        match Self::try_new(suit, rank) {
-            Ok(t) => t,
-            Err(_) => panic!(),
+        // not through `try_new`, the result can't be dropped in a const context
+        if !matches!(rank, (1..=13)) {
+            panic!()
         }
+        Self::new_unchecked(suit, rank)
     }
 
     #[inline]
@@ -40,16 +61,198 @@ impl Card {
 
     #[inline(always)]
     pub const fn new_unchecked(suit: CardSuit, rank: u32) -> Card {
-        Card { suit, rank }
+        Card(CardKind::Suited { suit, rank })
+    }
+
+    #[inline(always)]
+    pub const fn joker(effect: JokerEffect) -> Card {
+        Card(CardKind::Joker(effect))
+    }
+
+    pub fn custom(definition: CardDefinition) -> Card {
+        Card(CardKind::Custom(Arc::new(definition)))
+    }
+
+    /// `None` for jokers and custom cards
+    #[inline(always)]
+    pub fn suit(&self) -> Option<CardSuit> {
+        match self.0 {
+            CardKind::Suited { suit, .. } => Some(suit),
+            _ => None,
+        }
+    }
+
+    /// `None` for jokers and custom cards
+    #[inline(always)]
+    pub fn rank(&self) -> Option<u32> {
+        match self.0 {
+            CardKind::Suited { rank, .. } => Some(rank),
+            _ => None,
+        }
     }
 
     #[inline(always)]
-    pub fn suit(&self) -> CardSuit {
-        self.suit
+    pub fn joker_effect(&self) -> Option<JokerEffect> {
+        match self.0 {
+            CardKind::Joker(effect) => Some(effect),
+            _ => None,
+        }
     }
 
     #[inline(always)]
-    pub fn rank(&self) -> u32 {
-        self.rank
+    pub fn definition(&self) -> Option<&CardDefinition> {
+        match &self.0 {
+            CardKind::Custom(definition) => Some(definition),
+            _ => None,
+        }
+    }
+
+    #[inline(always)]
+    pub fn is_joker(&self) -> bool {
+        matches!(self.0, CardKind::Joker(_))
+    }
+}
+
+/// the letter of the suit in the card notation
+pub fn suit_symbol(suit: CardSuit) -> &'static str {
+    match suit {
+        CardSuit::Hearts => "H",
+        CardSuit::Diamonds => "D",
+        CardSuit::Clubs => "C",
+        CardSuit::Spades => "S",
+    }
+}
+
+pub(crate) fn rank_symbol(rank: u32) -> String {
+    match rank {
+        1 => "A".to_string(),
+        11 => "J".to_string(),
+        12 => "Q".to_string(),
+        13 => "K".to_string(),
+        rank => rank.to_string(),
+    }
+}
+
+fn joker_symbol(effect: JokerEffect) -> &'static str {
+    match effect {
+        JokerEffect::ClearRoom => "C",
+        JokerEffect::DoublePotion => "P",
+        JokerEffect::AnyMonster => "M",
+    }
+}
+
+/// short notation also used for asset names, e.g. "H10", "SK", "DA",
+/// jokers are "JK" followed by their effect, e.g. "JKC", custom cards are "@" followed by
+/// the id of their definition
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            CardKind::Suited { suit, rank } => {
+                write!(f, "{}{}", suit_symbol(*suit), rank_symbol(*rank))
+            }
+            CardKind::Joker(effect) => write!(f, "JK{}", joker_symbol(*effect)),
+            CardKind::Custom(definition) => write!(f, "@{}", definition.id),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseCardError(String);
+
+impl fmt::Display for ParseCardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid card notation '{}'", self.0)
+    }
+}
+
+impl std::error::Error for ParseCardError {}
+
+/// custom cards can't be parsed, their definition has to be looked up in the loaded assets
+impl FromStr for Card {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseCardError(s.to_string());
+
+        if let Some(effect) = s.strip_prefix("JK") {
+            return all::<JokerEffect>()
+                .find(|e| joker_symbol(*e) == effect)
+                .map(Card::joker)
+                .ok_or_else(err);
+        }
+
+        let suit = all::<CardSuit>()
+            .find(|suit| s.starts_with(suit_symbol(*suit)))
+            .ok_or_else(err)?;
+        let rank = (1..=13)
+            .find(|rank| s[1..] == rank_symbol(*rank))
+            .ok_or_else(err)?;
+        Ok(Card::new(suit, rank))
+    }
+}
+
+/// serialized as its notation
+impl Serialize for Card {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Card {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let notation = String::deserialize(deserializer)?;
+        notation.parse().map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suited_notation_round_trips() {
+        for suit in all::<CardSuit>() {
+            for rank in 1..=13 {
+                let card = Card::new(suit, rank);
+                let parsed: Card = card.to_string().parse().unwrap();
+                assert!(parsed == card, "{}", card);
+            }
+        }
+        assert_eq!(Card::new(CardSuit::Hearts, 10).to_string(), "H10");
+        assert_eq!(Card::new(CardSuit::Spades, 13).to_string(), "SK");
+        assert_eq!(Card::new(CardSuit::Diamonds, 1).to_string(), "DA");
+    }
+
+    #[test]
+    fn joker_notation_round_trips() {
+        for effect in all::<JokerEffect>() {
+            let card = Card::joker(effect);
+            let parsed: Card = card.to_string().parse().unwrap();
+            assert!(parsed == card, "{}", card);
+        }
+        assert_eq!(Card::joker(JokerEffect::ClearRoom).to_string(), "JKC");
+    }
+
+    #[test]
+    fn invalid_notation_is_rejected() {
+        for notation in [
+            "", "H", "H1", "H14", "X5", "JK", "JKZ", "SKK", "@sword", "h5",
+        ] {
+            assert!(notation.parse::<Card>().is_err(), "{}", notation);
+        }
+    }
+
+    #[test]
+    fn serde_uses_the_notation() {
+        let cards = vec![
+            Card::new(CardSuit::Clubs, 12),
+            Card::joker(JokerEffect::AnyMonster),
+        ];
+        let ron = ron::to_string(&cards).unwrap();
+        assert_eq!(ron, r#"["CQ","JKM"]"#);
+
+        let parsed: Vec<Card> = ron::from_str(&ron).unwrap();
+        assert!(parsed == cards);
+        assert!(ron::from_str::<Card>(r#""H0""#).is_err());
     }
 }
@@ -0,0 +1,165 @@
+use std::fmt;
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadedFolder, io::Reader},
+    ecs::system::SystemParam,
+    prelude::*,
+};
+use serde::Deserialize;
+
+use crate::{card::Card, effect::Effect};
+
+const DEFINITIONS_FOLDER: &str = "cards/custom";
+
+/// the part of the rules a custom card follows when played on the body
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize)]
+pub enum CardCategory {
+    Monster,
+    Weapon,
+    Potion,
+    /// only has its effects
+    Item,
+}
+
+/// a card beyond the standard 52, loaded from a `<id>.card.ron` file in `cards/custom`
+#[derive(Asset, TypePath, Clone, PartialEq, Eq, Debug)]
+pub struct CardDefinition {
+    /// the file name without extension
+    pub id: String,
+    pub name: String,
+    #[dependency]
+    pub art: Handle<Image>,
+    pub category: CardCategory,
+    /// how much it heals, blocks or hits, depending on the category
+    pub value: u32,
+    /// run once the card is played on the body, after the script of its category
+    pub effects: Vec<Effect>,
+}
+
+/// the contents of a `.card.ron` file, the art is a path relative to the assets folder
+#[derive(Deserialize)]
+struct CardDefinitionFile {
+    name: String,
+    art: String,
+    category: CardCategory,
+    value: u32,
+    #[serde(default)]
+    effects: Vec<Effect>,
+}
+
+/// failed to load a ron data file, card definitions or effect scripts
+#[derive(Debug)]
+pub enum DataFileError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+
+impl fmt::Display for DataFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataFileError::Io(err) => write!(f, "failed to read data file: {}", err),
+            DataFileError::Ron(err) => write!(f, "invalid data file: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for DataFileError {}
+
+impl From<std::io::Error> for DataFileError {
+    fn from(err: std::io::Error) -> Self {
+        DataFileError::Io(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for DataFileError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        DataFileError::Ron(err)
+    }
+}
+
+#[derive(Default)]
+struct CardDefinitionLoader;
+
+impl AssetLoader for CardDefinitionLoader {
+    type Asset = CardDefinition;
+    type Settings = ();
+    type Error = DataFileError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<CardDefinition, DataFileError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let file: CardDefinitionFile = ron::de::from_bytes(&bytes)?;
+
+        let file_name = load_context
+            .path()
+            .file_name()
+            .map_or(String::new(), |name| name.to_string_lossy().into_owned());
+        let id = file_name.trim_end_matches(".card.ron").to_string();
+
+        Ok(CardDefinition {
+            id,
+            name: file.name,
+            art: load_context.load(file.art),
+            category: file.category,
+            value: file.value,
+            effects: file.effects,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["card.ron"]
+    }
+}
+
+/// every definition found in `cards/custom`
+#[derive(Resource)]
+struct CardDefinitions(Handle<LoadedFolder>);
+
+/// the custom cards once their folder is loaded, see [`crate::rules::Rules::custom_cards`]
+#[derive(SystemParam)]
+pub struct CustomCards<'w> {
+    folder: Res<'w, CardDefinitions>,
+    folders: Res<'w, Assets<LoadedFolder>>,
+    definitions: Res<'w, Assets<CardDefinition>>,
+}
+
+impl CustomCards<'_> {
+    /// sorted by id so a seed deals the same dungeon whatever order the files load in
+    pub fn cards(&self) -> Vec<Card> {
+        let Some(folder) = self.folders.get(&self.folder.0) else {
+            warn!("custom cards are not loaded yet");
+            return Vec::new();
+        };
+
+        let mut definitions: Vec<_> = folder
+            .handles
+            .iter()
+            .filter_map(|handle| handle.id().try_typed::<CardDefinition>().ok())
+            .filter_map(|id| self.definitions.get(id))
+            .cloned()
+            .collect();
+        definitions.sort_by(|a, b| a.id.cmp(&b.id));
+        definitions.into_iter().map(Card::custom).collect()
+    }
+}
+
+pub struct CardDefinitionPlugin;
+
+impl Plugin for CardDefinitionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<CardDefinition>()
+            .register_asset_loader(CardDefinitionLoader)
+            .add_systems(PreStartup, load_definitions);
+    }
+}
+
+fn load_definitions(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(CardDefinitions(
+        asset_server.load_folder(DEFINITIONS_FOLDER),
+    ));
+}
@@ -2,16 +2,28 @@ use bevy::prelude::*;
 
 use crate::{
     card::Card,
-    card_filter::CardFilter,
-    card_slot::{CardSlot, PlacedOnSlot, PlacementOfCard},
+    card_lock::Locked,
+    card_select::Selected,
+    card_slot::{CardPlacement, CardSlot, PlacedOnSlot},
+    menu::{GamePhase, game_running},
+    sprite_repr::CARD_SIZE,
 };
 
-/// marks if a card can be moved
 #[derive(Component)]
-struct Locked;
+struct DragStartPoint(Vec3);
 
+/// selected card moved along with the dragged one, stacked on top of it in this order on drop
 #[derive(Component)]
-struct DragStartPoint(Vec3);
+struct DragFollower(usize);
+
+/// card carried by a dragged pile, only unpickable for the duration of the drag
+#[derive(Component)]
+struct DragPassenger;
+
+/// cards released within this distance of the slot (or overlapping it) snap onto it
+#[derive(Component)]
+#[require(CardSlot)]
+pub struct SnapRadius(pub f32);
 
 pub struct CardDragDropPlugin;
 
@@ -24,73 +36,236 @@ impl Plugin for CardDragDropPlugin {
     }
 }
 
+/// the dragged card followed by the other selected cards, skipping those already carried by
+/// another member of the group
+fn drag_group(
+    leader: Entity,
+    selected: &Query<(Entity, &Selected), Without<Locked>>,
+    parents: &Query<&ChildOf>,
+) -> Vec<Entity> {
+    if !selected.contains(leader) {
+        return vec![leader];
+    }
+
+    let mut members: Vec<_> = selected.iter().filter(|(e, _)| *e != leader).collect();
+    members.sort_by_key(|(_, s)| s.0);
+
+    let mut group = vec![leader];
+    group.extend(members.into_iter().map(|(e, _)| e));
+
+    let carried = |e: Entity| parents.iter_ancestors(e).any(|a| group.contains(&a));
+    group.iter().copied().filter(|e| !carried(*e)).collect()
+}
+
+fn sorted_followers(followers: &Query<(Entity, &DragFollower)>) -> Vec<Entity> {
+    let mut followers: Vec<_> = followers.iter().collect();
+    followers.sort_by_key(|(_, f)| f.0);
+    followers.into_iter().map(|(e, _)| e).collect()
+}
+
+/// places the leader on `slot` and stacks the followers on top of it, all or nothing, a group
+/// only goes on an [`AcceptsStacks`](crate::card_slot::AcceptsStacks) slot
+fn place_group(
+    leader: Entity,
+    followers: &[Entity],
+    slot: Entity,
+    placement: &CardPlacement,
+    commands: &mut Commands,
+) -> bool {
+    let mut links = vec![(leader, slot)];
+    let mut below = leader;
+    for follower in followers {
+        links.push((*follower, below));
+        below = *follower;
+    }
+
+    // only the bottom card goes on the slot, the others are stacked on the card below them
+    if !placement.can_place(leader, slot)
+        || (!followers.is_empty() && !placement.accepts_stacks(slot))
+        || !links[1..]
+            .iter()
+            .all(|(card, below)| placement.can_stack(*card, *below))
+    {
+        return false;
+    }
+
+    for (card, slot) in links {
+        commands
+            .entity(card)
+            .remove::<DragStartPoint>()
+            .insert(PlacedOnSlot(slot));
+    }
+    true
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 fn handle_drag_start(
-    tr: Trigger<Pointer<DragStart>>,
+    mut tr: Trigger<Pointer<DragStart>>,
     cards: Query<&Transform, (With<Card>, Without<Locked>)>,
+    selected: Query<(Entity, &Selected), Without<Locked>>,
+    parents: Query<&ChildOf>,
+    children: Query<&Children>,
+    pickable_cards: Query<(), (With<Card>, With<Pickable>)>,
+    phase: Option<Res<State<GamePhase>>>,
     mut commands: Commands,
 ) {
-    let entity = tr.target();
-    let Ok(trf) = cards.get(entity) else {
+    let leader = tr.target();
+    if !cards.contains(leader) || !game_running(phase) {
         return;
-    };
-    commands
-        .entity(entity)
-        .remove::<Pickable>()
-        .insert(DragStartPoint(trf.translation));
+    }
+    tr.propagate(false);
+
+    for (idx, member) in drag_group(leader, &selected, &parents)
+        .into_iter()
+        .enumerate()
+    {
+        let trf = cards.get(member).unwrap();
+        commands
+            .entity(member)
+            .remove::<Pickable>()
+            .insert(DragStartPoint(trf.translation));
+        if member != leader {
+            commands.entity(member).insert(DragFollower(idx));
+        }
+
+        for passenger in children.iter_descendants(member) {
+            if pickable_cards.contains(passenger) {
+                commands
+                    .entity(passenger)
+                    .remove::<Pickable>()
+                    .insert(DragPassenger);
+            }
+        }
+    }
 }
 
 fn handle_drag(
-    tr: Trigger<Pointer<Drag>>,
-    mut tf: Query<&mut Transform, (With<Card>, Without<Locked>)>,
+    mut tr: Trigger<Pointer<Drag>>,
+    mut tf: Query<&mut Transform, (With<Card>, With<DragStartPoint>)>,
+    followers: Query<Entity, With<DragFollower>>,
+    phase: Option<Res<State<GamePhase>>>,
 ) {
-    let Ok(mut tf) = tf.get_mut(tr.target()) else {
+    let leader = tr.target();
+    if !tf.contains(leader) || !game_running(phase) {
         return;
-    };
-    tf.translation += vec3(tr.delta.x, -tr.delta.y, 0.0);
+    }
+    tr.propagate(false);
+
+    let delta = vec3(tr.delta.x, -tr.delta.y, 0.0);
+    for member in std::iter::once(leader).chain(&followers) {
+        if let Ok(mut tf) = tf.get_mut(member) {
+            tf.translation += delta;
+        }
+    }
 }
 
 fn handle_drag_drop(
-    tr: Trigger<Pointer<DragDrop>>,
-    cards: Query<&Card, Without<Locked>>,
-    slots: Query<Option<&CardFilter>, (With<CardSlot>, Without<PlacementOfCard>)>,
+    mut tr: Trigger<Pointer<DragDrop>>,
+    followers: Query<(Entity, &DragFollower)>,
+    placement: CardPlacement,
+    phase: Option<Res<State<GamePhase>>>,
     mut commands: Commands,
 ) {
-    let entity = tr.dropped;
-    let Ok(card) = cards.get(entity) else {
+    // a drag carried over into the pause goes back where it started
+    if !game_running(phase) {
         return;
-    };
-
+    }
+    let leader = tr.dropped;
     let slot = tr.target();
-    let Ok(cf) = slots.get(slot) else {
-        return;
-    };
+    let followers = sorted_followers(&followers);
 
-    if cf.is_some_and(|cf| !cf.check(card)) {
-        return;
+    if place_group(leader, &followers, slot, &placement, &mut commands) {
+        tr.propagate(false);
     }
-
-    commands
-        .entity(entity)
-        .remove::<DragStartPoint>()
-        .insert(PlacedOnSlot(slot));
 }
 
-#[allow(clippy::type_complexity)]
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 fn handle_drag_end(
-    tr: Trigger<Pointer<DragEnd>>,
-    mut cards: Query<Option<(&mut Transform, &DragStartPoint)>, (With<Card>, Without<Locked>)>,
+    mut tr: Trigger<Pointer<DragEnd>>,
+    mut cards: Query<(&mut Transform, Option<&DragStartPoint>), With<Card>>,
+    transform_helper: TransformHelper,
+    followers: Query<(Entity, &DragFollower)>,
+    passengers: Query<Entity, With<DragPassenger>>,
+    snap_slots: Query<(Entity, &GlobalTransform, &SnapRadius)>,
+    sprites: Query<&Sprite>,
+    placement: CardPlacement,
+    phase: Option<Res<State<GamePhase>>>,
     mut commands: Commands,
 ) {
-    let Ok(card) = cards.get_mut(tr.target()) else {
+    let running = game_running(phase);
+    let leader = tr.target();
+    let Ok((_, start)) = cards.get(leader) else {
         return;
     };
+    tr.propagate(false);
 
-    commands.entity(tr.target()).insert(Pickable::default());
+    // the GlobalTransform still lags behind the last drag move until the next propagation
+    let Ok(leader_gtf) = transform_helper.compute_global_transform(leader) else {
+        return;
+    };
+    let card_pos = leader_gtf.translation().truncate();
+    let dropped = start.is_none();
+    let followers = sorted_followers(&followers);
 
-    let Some((mut trf, sp)) = card else {
+    commands.entity(leader).insert(Pickable::default());
+    for follower in &followers {
+        commands
+            .entity(*follower)
+            .remove::<DragFollower>()
+            .insert(Pickable::default());
+    }
+    for passenger in &passengers {
+        commands
+            .entity(passenger)
+            .remove::<DragPassenger>()
+            .insert(Pickable::default());
+    }
+
+    if dropped {
         return;
+    }
+
+    let size_of = |e: Entity| {
+        sprites
+            .get(e)
+            .ok()
+            .and_then(|s| s.custom_size)
+            .unwrap_or(CARD_SIZE)
     };
 
-    commands.entity(tr.target()).remove::<DragStartPoint>();
-    trf.translation = sp.0
+    let card_rect = Rect::from_center_size(card_pos, size_of(leader));
+
+    let snap_target = snap_slots
+        .iter()
+        .filter(|_| running)
+        .filter(|(slot, ..)| placement.can_place(leader, *slot))
+        .filter_map(|(slot, slot_gtf, radius)| {
+            let slot_pos = slot_gtf.translation().truncate();
+            let overlap = card_rect.intersect(Rect::from_center_size(slot_pos, size_of(slot)));
+            let overlap_area = if overlap.is_empty() {
+                0.0
+            } else {
+                overlap.width() * overlap.height()
+            };
+            let distance = card_pos.distance(slot_pos);
+
+            (overlap_area > 0.0 || distance <= radius.0).then_some((slot, overlap_area, distance))
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1).then(b.2.total_cmp(&a.2)))
+        .map(|(slot, ..)| slot);
+
+    if snap_target
+        .is_some_and(|slot| place_group(leader, &followers, slot, &placement, &mut commands))
+    {
+        return;
+    }
+
+    // rejected, every member of the group returns to where it was picked up
+    for member in std::iter::once(leader).chain(followers) {
+        let Ok((mut trf, Some(sp))) = cards.get_mut(member) else {
+            continue;
+        };
+        trf.translation = sp.0;
+        commands.entity(member).remove::<DragStartPoint>();
+    }
 }
@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 
-use crate::card::{Card, CardSuit};
+use crate::{
+    card::{Card, CardSuit, JokerEffect},
+    card_definition::CardCategory,
+};
 
 #[derive(Component, Default, Clone)]
 pub struct CardFilter {
@@ -9,13 +12,37 @@ pub struct CardFilter {
     rank_min: Option<u32>,
     /// included
     rank_max: Option<u32>,
+    /// jokers ignore suit and rank, they pass only if their effect is listed
+    jokers: Vec<JokerEffect>,
+    /// custom cards pass if their category is listed, their value is checked as the rank
+    categories: Vec<CardCategory>,
+    /// aces are checked as this rank when set, as they rank above the kings in some rules
+    ace_rank: Option<u32>,
 }
 
 impl CardFilter {
     pub fn check(&self, card: &Card) -> bool {
-        self.suits.contains(&card.suit())
-            && self.rank_min.is_none_or(|min| min <= card.rank())
-            && self.rank_max.is_none_or(|min| min >= card.rank())
+        if let Some(effect) = card.joker_effect() {
+            return self.jokers.contains(&effect);
+        }
+
+        let (matches, rank) = match (card.definition(), card.suit(), card.rank()) {
+            (Some(definition), _, _) => (
+                self.categories.contains(&definition.category),
+                definition.value,
+            ),
+            (None, Some(suit), Some(1)) => (self.suits.contains(&suit), self.ace_rank.unwrap_or(1)),
+            (None, Some(suit), Some(rank)) => (self.suits.contains(&suit), rank),
+            _ => return false,
+        };
+
+        matches
+            && self.rank_min.is_none_or(|min| min <= rank)
+            && self.rank_max.is_none_or(|min| min >= rank)
+    }
+
+    pub fn max_rank(&self) -> Option<u32> {
+        self.rank_max
     }
 
     pub fn empty() -> Self {
@@ -27,6 +54,16 @@ impl CardFilter {
         self
     }
 
+    pub fn with_jokers(mut self, effects: impl IntoIterator<Item = JokerEffect>) -> Self {
+        self.jokers = effects.into_iter().collect();
+        self
+    }
+
+    pub fn with_categories(mut self, categories: impl IntoIterator<Item = CardCategory>) -> Self {
+        self.categories = categories.into_iter().collect();
+        self
+    }
+
     pub fn with_min_rank(mut self, min: u32) -> Self {
         self.rank_min = Some(min);
         self
@@ -37,9 +74,44 @@ impl CardFilter {
         self
     }
 
+    pub fn with_ace_rank(mut self, rank: u32) -> Self {
+        self.ace_rank = Some(rank);
+        self
+    }
+
     pub fn with_exact_rank(mut self, rank: u32) -> Self {
         self.rank_min = Some(rank);
         self.rank_max = Some(rank);
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jokers_pass_only_if_listed() {
+        let clear_room = Card::joker(JokerEffect::ClearRoom);
+        let any_monster = Card::joker(JokerEffect::AnyMonster);
+
+        let filter = CardFilter::empty().with_suit([CardSuit::Spades]);
+        assert!(!filter.check(&clear_room));
+        assert!(!filter.check(&any_monster));
+
+        let filter = filter.with_jokers([JokerEffect::AnyMonster]);
+        assert!(!filter.check(&clear_room));
+        assert!(filter.check(&any_monster));
+    }
+
+    #[test]
+    fn jokers_ignore_suit_and_rank() {
+        let filter = CardFilter::empty()
+            .with_suit([CardSuit::Spades])
+            .with_jokers([JokerEffect::AnyMonster])
+            .with_max_rank(2);
+        assert!(filter.check(&Card::joker(JokerEffect::AnyMonster)));
+        assert!(filter.check(&Card::new(CardSuit::Spades, 2)));
+        assert!(!filter.check(&Card::new(CardSuit::Spades, 5)));
+    }
+}
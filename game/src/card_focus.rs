@@ -0,0 +1,194 @@
+use bevy::{
+    color::palettes::css::{LIME, YELLOW},
+    prelude::*,
+};
+
+use crate::{
+    card::Card,
+    card_lock::Locked,
+    card_slot::{CardPlacement, CardSlot, PlaceOnSlot},
+    menu::GamePhase,
+    sprite_repr::CARD_SIZE,
+};
+
+/// keyboard / gamepad cursor used to play cards without a pointer
+#[derive(Resource, Default, Clone, Copy)]
+pub enum CardCursor {
+    #[default]
+    Idle,
+    Browsing(Entity),
+    Holding {
+        card: Entity,
+        slot: Option<Entity>,
+    },
+}
+
+#[derive(Event, Clone, Copy)]
+pub enum CursorAction {
+    Prev,
+    Next,
+    Confirm,
+    Cancel,
+}
+
+pub struct CardFocusPlugin;
+
+impl Plugin for CardFocusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CardCursor>()
+            .add_event::<CursorAction>()
+            .add_systems(
+                Update,
+                (
+                    (read_keyboard, read_gamepads).run_if(in_state(GamePhase::Running)),
+                    apply_cursor_actions,
+                    draw_cursor,
+                )
+                    .chain(),
+            );
+    }
+}
+
+fn read_keyboard(keys: Res<ButtonInput<KeyCode>>, mut actions: EventWriter<CursorAction>) {
+    let bindings = [
+        (KeyCode::ArrowLeft, CursorAction::Prev),
+        (KeyCode::ArrowUp, CursorAction::Prev),
+        (KeyCode::ArrowRight, CursorAction::Next),
+        (KeyCode::ArrowDown, CursorAction::Next),
+        (KeyCode::Enter, CursorAction::Confirm),
+        (KeyCode::Space, CursorAction::Confirm),
+        (KeyCode::Backspace, CursorAction::Cancel),
+    ];
+
+    for (key, action) in bindings {
+        if keys.just_pressed(key) {
+            actions.write(action);
+        }
+    }
+}
+
+fn read_gamepads(gamepads: Query<&Gamepad>, mut actions: EventWriter<CursorAction>) {
+    let bindings = [
+        (GamepadButton::DPadLeft, CursorAction::Prev),
+        (GamepadButton::DPadUp, CursorAction::Prev),
+        (GamepadButton::DPadRight, CursorAction::Next),
+        (GamepadButton::DPadDown, CursorAction::Next),
+        (GamepadButton::South, CursorAction::Confirm),
+        (GamepadButton::East, CursorAction::Cancel),
+    ];
+
+    for gamepad in &gamepads {
+        for (button, action) in bindings {
+            if gamepad.just_pressed(button) {
+                actions.write(action);
+            }
+        }
+    }
+}
+
+/// returns the entity after / before `current` in left to right order, wrapping around
+fn cycle(sorted: &[Entity], current: Option<Entity>, action: CursorAction) -> Option<Entity> {
+    let len = sorted.len();
+    if len == 0 {
+        return None;
+    }
+
+    let Some(idx) = current.and_then(|c| sorted.iter().position(|e| *e == c)) else {
+        return Some(sorted[0]);
+    };
+
+    match action {
+        CursorAction::Prev => Some(sorted[(idx + len - 1) % len]),
+        CursorAction::Next => Some(sorted[(idx + 1) % len]),
+        _ => Some(sorted[idx]),
+    }
+}
+
+fn sorted_by_x(items: impl Iterator<Item = (Entity, Vec3)>) -> Vec<Entity> {
+    let mut items: Vec<_> = items.collect();
+    items.sort_by(|(_, a), (_, b)| a.x.total_cmp(&b.x).then(b.y.total_cmp(&a.y)));
+    items.into_iter().map(|(e, _)| e).collect()
+}
+
+#[allow(clippy::type_complexity)]
+fn apply_cursor_actions(
+    mut actions: EventReader<CursorAction>,
+    mut cursor: ResMut<CardCursor>,
+    cards: Query<(Entity, &GlobalTransform), (With<Card>, Without<Locked>)>,
+    slots: Query<(Entity, &GlobalTransform), With<CardSlot>>,
+    placement: CardPlacement,
+    mut commands: Commands,
+) {
+    // the focused card may have been played or locked in the meantime
+    match *cursor {
+        CardCursor::Browsing(card) | CardCursor::Holding { card, .. } if !cards.contains(card) => {
+            *cursor = CardCursor::Idle;
+        }
+        _ => {}
+    }
+
+    for action in actions.read().copied() {
+        let focusable = || sorted_by_x(cards.iter().map(|(e, tf)| (e, tf.translation())));
+        let targets = |card: Entity| {
+            sorted_by_x(
+                slots
+                    .iter()
+                    .filter(|(slot, _)| placement.can_place(card, *slot))
+                    .map(|(e, tf)| (e, tf.translation())),
+            )
+        };
+
+        *cursor = match (*cursor, action) {
+            (CardCursor::Idle, CursorAction::Cancel) => CardCursor::Idle,
+            (CardCursor::Idle, _) => {
+                cycle(&focusable(), None, action).map_or(CardCursor::Idle, CardCursor::Browsing)
+            }
+            (CardCursor::Browsing(_), CursorAction::Cancel) => CardCursor::Idle,
+            (CardCursor::Browsing(card), CursorAction::Confirm) => CardCursor::Holding {
+                card,
+                slot: targets(card).first().copied(),
+            },
+            (CardCursor::Browsing(card), _) => cycle(&focusable(), Some(card), action)
+                .map_or(CardCursor::Idle, CardCursor::Browsing),
+            (CardCursor::Holding { card, .. }, CursorAction::Cancel) => CardCursor::Browsing(card),
+            (CardCursor::Holding { card, slot }, CursorAction::Confirm) => match slot {
+                Some(slot) => {
+                    commands.entity(card).trigger(PlaceOnSlot(slot));
+                    CardCursor::Idle
+                }
+                None => CardCursor::Holding { card, slot },
+            },
+            (CardCursor::Holding { card, slot }, _) => CardCursor::Holding {
+                card,
+                slot: cycle(&targets(card), slot, action),
+            },
+        };
+    }
+}
+
+fn draw_cursor(cursor: Res<CardCursor>, transforms: Query<&GlobalTransform>, mut gizmos: Gizmos) {
+    let pos = |e: Entity| transforms.get(e).ok().map(|tf| tf.translation().truncate());
+    let outline = CARD_SIZE + Vec2::splat(12.0);
+
+    match *cursor {
+        CardCursor::Idle => {}
+        CardCursor::Browsing(card) => {
+            if let Some(p) = pos(card) {
+                gizmos.rect_2d(p, outline, YELLOW);
+            }
+        }
+        CardCursor::Holding { card, slot } => {
+            let card_pos = pos(card);
+            let slot_pos = slot.and_then(pos);
+            if let Some(p) = card_pos {
+                gizmos.rect_2d(p, outline, YELLOW);
+            }
+            if let Some(p) = slot_pos {
+                gizmos.rect_2d(p, outline, LIME);
+            }
+            if let (Some(from), Some(to)) = (card_pos, slot_pos) {
+                gizmos.arrow_2d(from, to, LIME);
+            }
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use bevy::prelude::*;
+
+use crate::{
+    assets::AssetHandles,
+    card::Card,
+    card_slot::{CardSlot, PlacedOnSlot},
+    discard::InDiscardPile,
+    sprite_repr::CARD_SIZE,
+};
+
+const LOCKED_TINT: Color = Color::srgba(0.0, 0.0, 0.0, 0.45);
+const PADLOCK_SIZE: Vec2 = Vec2::splat(40.0);
+
+/// marks if a card can be moved
+#[derive(Component)]
+pub struct Locked;
+
+/// locks the targeted card
+#[derive(Event)]
+pub struct LockCard;
+
+/// unlocks the targeted card
+#[derive(Event)]
+pub struct UnlockCard;
+
+/// cards placed on this slot are locked until they leave it
+#[derive(Component, Default)]
+#[require(CardSlot)]
+pub struct LocksPlacedCards;
+
+/// the [`Locked`] comes from a [`LocksPlacedCards`] slot and is removed when the card leaves it,
+/// an explicit [`LockCard`] replaces it
+#[derive(Component)]
+struct LockedBySlot;
+
+#[derive(Component)]
+struct LockedOverlay(Entity);
+
+pub struct CardLockPlugin;
+
+impl Plugin for CardLockPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(handle_lock_card)
+            .add_observer(handle_unlock_card)
+            .add_observer(handle_placed_on_locking_slot)
+            .add_observer(handle_left_locking_slot)
+            .add_observer(handle_locked_added)
+            .add_observer(handle_locked_removed);
+    }
+}
+
+fn handle_lock_card(tr: Trigger<LockCard>, cards: Query<(), With<Card>>, mut commands: Commands) {
+    let entity = tr.target();
+    if cards.contains(entity) {
+        commands
+            .entity(entity)
+            .insert(Locked)
+            .remove::<LockedBySlot>();
+    }
+}
+
+fn handle_unlock_card(tr: Trigger<UnlockCard>, mut commands: Commands) {
+    commands
+        .entity(tr.target())
+        .remove::<(Locked, LockedBySlot)>();
+}
+
+fn handle_placed_on_locking_slot(
+    tr: Trigger<OnInsert, PlacedOnSlot>,
+    placed_on: Query<(&PlacedOnSlot, Has<Locked>), With<Card>>,
+    locking_slots: Query<(), With<LocksPlacedCards>>,
+    mut commands: Commands,
+) {
+    let card = tr.target();
+    let Ok((placed_on, locked)) = placed_on.get(card) else {
+        return;
+    };
+    // an explicit lock outlives the slot
+    if locking_slots.contains(placed_on.0) && !locked {
+        commands.entity(card).insert((Locked, LockedBySlot));
+    }
+}
+
+fn handle_left_locking_slot(
+    tr: Trigger<OnReplace, PlacedOnSlot>,
+    locked_by_slot: Query<(), With<LockedBySlot>>,
+    mut commands: Commands,
+) {
+    let card = tr.target();
+    if !locked_by_slot.contains(card) {
+        return;
+    }
+
+    if let Ok(mut ec) = commands.get_entity(card) {
+        ec.try_remove::<(Locked, LockedBySlot)>();
+    }
+}
+
+fn handle_locked_added(
+    tr: Trigger<OnAdd, Locked>,
+    discarded: Query<(), With<InDiscardPile>>,
+    assets: Res<AssetHandles>,
+    mut commands: Commands,
+) {
+    let card = tr.target();
+    // the pile is locked as a whole, its cards aren't dimmed one by one
+    if discarded.contains(card) {
+        return;
+    }
+    let overlay = commands
+        .spawn((
+            Name::new("Locked Overlay"),
+            ChildOf(card),
+            Sprite {
+                color: LOCKED_TINT,
+                custom_size: Some(CARD_SIZE),
+                ..default()
+            },
+            Transform::from_xyz(0.0, 0.0, 0.5),
+            Pickable::IGNORE,
+            children![(
+                Sprite {
+                    image: assets.get_padlock_image(),
+                    custom_size: Some(PADLOCK_SIZE),
+                    ..default()
+                },
+                Transform::from_xyz(
+                    (CARD_SIZE.x - PADLOCK_SIZE.x) / 2.0 - 6.0,
+                    (CARD_SIZE.y - PADLOCK_SIZE.y) / 2.0 - 6.0,
+                    0.1,
+                ),
+                Pickable::IGNORE,
+            )],
+        ))
+        .id();
+    commands.entity(card).insert(LockedOverlay(overlay));
+}
+
+fn handle_locked_removed(
+    tr: Trigger<OnRemove, Locked>,
+    overlays: Query<&LockedOverlay>,
+    mut commands: Commands,
+) {
+    let card = tr.target();
+    let Ok(overlay) = overlays.get(card) else {
+        return;
+    };
+
+    if let Ok(mut ec) = commands.get_entity(overlay.0) {
+        ec.try_despawn();
+    }
+    if let Ok(mut ec) = commands.get_entity(card) {
+        ec.try_remove::<LockedOverlay>();
+    }
+}
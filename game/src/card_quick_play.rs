@@ -0,0 +1,204 @@
+use bevy::{
+    color::palettes::css::{DARK_SLATE_GRAY, SLATE_GRAY},
+    ecs::system::SystemParam,
+    prelude::*,
+};
+
+use crate::{
+    card::Card,
+    card_lock::Locked,
+    card_slot::{CardPlacement, CardSlot, PlaceOnSlot},
+    menu::{GamePhase, game_running},
+};
+
+const DOUBLE_CLICK_SECS: f32 = 0.35;
+
+/// slot reachable through quick play, the legal target with the highest priority is the
+/// default action of a card
+#[derive(Component)]
+#[require(CardSlot)]
+pub struct QuickPlayTarget(pub i32);
+
+#[derive(SystemParam)]
+pub struct QuickPlay<'w, 's> {
+    targets: Query<'w, 's, (Entity, &'static QuickPlayTarget, Option<&'static Name>)>,
+    placement: CardPlacement<'w, 's>,
+}
+
+impl QuickPlay<'_, '_> {
+    pub fn legal_targets(
+        &self,
+        card: Entity,
+    ) -> impl Iterator<Item = (Entity, i32, Option<&Name>)> {
+        self.targets
+            .iter()
+            .filter(move |(slot, ..)| self.placement.can_place(card, *slot))
+            .map(|(slot, prio, name)| (slot, prio.0, name))
+    }
+
+    pub fn default_target(&self, card: Entity) -> Option<Entity> {
+        self.legal_targets(card)
+            .max_by_key(|(_, prio, _)| *prio)
+            .map(|(slot, ..)| slot)
+    }
+}
+
+#[derive(Resource, Default)]
+struct LastClick(Option<(Entity, f32)>);
+
+#[derive(Component)]
+struct ContextMenu;
+
+#[derive(Component)]
+struct ContextMenuEntry {
+    card: Entity,
+    slot: Entity,
+}
+
+pub struct CardQuickPlayPlugin;
+
+impl Plugin for CardQuickPlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LastClick>()
+            .add_observer(handle_click)
+            .add_systems(
+                Update,
+                (
+                    close_context_menu,
+                    handle_context_menu_entries.run_if(game_running),
+                )
+                    .chain(),
+            );
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_click(
+    mut tr: Trigger<Pointer<Click>>,
+    cards: Query<(), (With<Card>, Without<Locked>)>,
+    quick_play: QuickPlay,
+    mut last_click: ResMut<LastClick>,
+    time: Res<Time>,
+    keys: Res<ButtonInput<KeyCode>>,
+    menus: Query<Entity, With<ContextMenu>>,
+    phase: Option<Res<State<GamePhase>>>,
+    mut commands: Commands,
+) {
+    let card = tr.target();
+    if !cards.contains(card) || !game_running(phase) {
+        return;
+    }
+    tr.propagate(false);
+
+    match tr.button {
+        // shift clicks belong to the selection
+        PointerButton::Primary if keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) => {}
+        PointerButton::Primary => {
+            let now = time.elapsed_secs();
+            let is_double = last_click
+                .0
+                .is_some_and(|(e, t)| e == card && now - t <= DOUBLE_CLICK_SECS);
+
+            if !is_double {
+                last_click.0 = Some((card, now));
+                return;
+            }
+
+            last_click.0 = None;
+            if let Some(slot) = quick_play.default_target(card) {
+                commands.entity(card).trigger(PlaceOnSlot(slot));
+            }
+        }
+        PointerButton::Secondary => {
+            for menu in &menus {
+                commands.entity(menu).despawn();
+            }
+            spawn_context_menu(
+                &mut commands,
+                card,
+                tr.pointer_location.position,
+                &quick_play,
+            );
+        }
+        PointerButton::Middle => {}
+    }
+}
+
+fn spawn_context_menu(commands: &mut Commands, card: Entity, pos: Vec2, quick_play: &QuickPlay) {
+    let mut targets: Vec<_> = quick_play.legal_targets(card).collect();
+    if targets.is_empty() {
+        return;
+    }
+    targets.sort_by_key(|(_, prio, _)| -prio);
+
+    let menu = commands
+        .spawn((
+            Name::new("Context Menu"),
+            ContextMenu,
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(pos.x),
+                top: Val::Px(pos.y),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(4.0)),
+                row_gap: Val::Px(2.0),
+                ..default()
+            },
+            BackgroundColor(DARK_SLATE_GRAY.into()),
+            GlobalZIndex(10),
+        ))
+        .id();
+
+    for (slot, _, name) in targets {
+        let label = name.map_or("Slot".to_string(), |n| n.to_string());
+        commands.spawn((
+            ChildOf(menu),
+            ContextMenuEntry { card, slot },
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(12.0), Val::Px(4.0)),
+                ..default()
+            },
+            BackgroundColor(Color::NONE),
+            children![(Text::new(label), TextFont::from_font_size(18.0))],
+        ));
+    }
+}
+
+fn handle_context_menu_entries(
+    entries: Query<(&Interaction, &ContextMenuEntry, &mut BackgroundColor), Changed<Interaction>>,
+    menus: Query<Entity, With<ContextMenu>>,
+    mut commands: Commands,
+) {
+    for (interaction, entry, mut bg) in entries {
+        match interaction {
+            Interaction::Pressed => {
+                commands.entity(entry.card).trigger(PlaceOnSlot(entry.slot));
+                for menu in &menus {
+                    commands.entity(menu).despawn();
+                }
+            }
+            Interaction::Hovered => bg.0 = SLATE_GRAY.into(),
+            Interaction::None => bg.0 = Color::NONE,
+        }
+    }
+}
+
+fn close_context_menu(
+    mouse: Res<ButtonInput<MouseButton>>,
+    entries: Query<&Interaction, With<ContextMenuEntry>>,
+    menus: Query<Entity, With<ContextMenu>>,
+    mut commands: Commands,
+) {
+    if !mouse.any_just_pressed([MouseButton::Left, MouseButton::Right]) {
+        return;
+    }
+
+    if entries.iter().any(|i| *i != Interaction::None) {
+        return;
+    }
+
+    for menu in &menus {
+        commands.entity(menu).despawn();
+    }
+}
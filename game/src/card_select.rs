@@ -0,0 +1,151 @@
+use bevy::{
+    color::palettes::css::{AQUA, WHITE},
+    picking::{hover::HoverMap, pointer::PointerId},
+    prelude::*,
+    window::PrimaryWindow,
+};
+
+use crate::{
+    card::Card,
+    card_lock::Locked,
+    menu::{GamePhase, game_running},
+    sprite_repr::CARD_SIZE,
+};
+
+/// marks a card as part of the current selection, the value is the selection order
+#[derive(Component)]
+pub struct Selected(pub u32);
+
+#[derive(Resource, Default)]
+struct SelectionState {
+    next: u32,
+    lasso_start: Option<Vec2>,
+}
+
+pub struct CardSelectPlugin;
+
+impl Plugin for CardSelectPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SelectionState>()
+            .add_observer(handle_shift_click)
+            .add_observer(handle_locked_deselect)
+            .add_systems(
+                Update,
+                (
+                    update_lasso.run_if(in_state(GamePhase::Running)),
+                    draw_selection,
+                )
+                    .chain(),
+            );
+    }
+}
+
+fn shift_held(keys: &ButtonInput<KeyCode>) -> bool {
+    keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight])
+}
+
+fn handle_shift_click(
+    mut tr: Trigger<Pointer<Click>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    cards: Query<Has<Selected>, (With<Card>, Without<Locked>)>,
+    mut state: ResMut<SelectionState>,
+    phase: Option<Res<State<GamePhase>>>,
+    mut commands: Commands,
+) {
+    if tr.button != PointerButton::Primary || !shift_held(&keys) || !game_running(phase) {
+        return;
+    }
+    let card = tr.target();
+    let Ok(selected) = cards.get(card) else {
+        return;
+    };
+    tr.propagate(false);
+
+    if selected {
+        commands.entity(card).remove::<Selected>();
+    } else {
+        commands.entity(card).insert(Selected(state.next));
+        state.next += 1;
+    }
+}
+
+fn handle_locked_deselect(tr: Trigger<OnAdd, Locked>, mut commands: Commands) {
+    commands.entity(tr.target()).try_remove::<Selected>();
+}
+
+fn cursor_world_pos(
+    windows: &Query<&Window, With<PrimaryWindow>>,
+    cameras: &Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+) -> Option<Vec2> {
+    let cursor = windows.single().ok()?.cursor_position()?;
+    let (camera, cam_tf) = cameras.single().ok()?;
+    camera.viewport_to_world_2d(cam_tf, cursor).ok()
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+fn update_lasso(
+    mouse: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    hover_map: Res<HoverMap>,
+    backgrounds: Query<(), With<Window>>,
+    cards: Query<(Entity, &GlobalTransform, Has<Selected>), (With<Card>, Without<Locked>)>,
+    mut state: ResMut<SelectionState>,
+    mut commands: Commands,
+) {
+    let Some(pos) = cursor_world_pos(&windows, &cameras) else {
+        return;
+    };
+
+    // only on empty background, not on cards, slots or ui
+    if mouse.just_pressed(MouseButton::Left) {
+        let over_something = hover_map
+            .get(&PointerId::Mouse)
+            .is_some_and(|hits| hits.keys().any(|e| !backgrounds.contains(*e)));
+        if !over_something {
+            state.lasso_start = Some(pos);
+        }
+    }
+
+    if !mouse.just_released(MouseButton::Left) {
+        return;
+    }
+    let Some(start) = state.lasso_start.take() else {
+        return;
+    };
+
+    let lasso = Rect::from_corners(start, pos);
+    let additive = shift_held(&keys);
+
+    for (card, tf, selected) in &cards {
+        let inside = lasso.contains(tf.translation().truncate());
+        if inside && !selected {
+            commands.entity(card).insert(Selected(state.next));
+            state.next += 1;
+        } else if !inside && selected && !additive {
+            commands.entity(card).remove::<Selected>();
+        }
+    }
+}
+
+fn draw_selection(
+    state: Res<SelectionState>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    selected: Query<&GlobalTransform, With<Selected>>,
+    mut gizmos: Gizmos,
+) {
+    for tf in &selected {
+        gizmos.rect_2d(
+            tf.translation().truncate(),
+            CARD_SIZE + Vec2::splat(6.0),
+            AQUA,
+        );
+    }
+
+    if let (Some(start), Some(pos)) = (state.lasso_start, cursor_world_pos(&windows, &cameras)) {
+        let lasso = Rect::from_corners(start, pos);
+        gizmos.rect_2d(lasso.center(), lasso.size(), WHITE);
+    }
+}
@@ -1,6 +1,8 @@
-use bevy::prelude::*;
+use bevy::{ecs::system::SystemParam, prelude::*};
 use enum_iterator::Sequence;
 
+use crate::{card::Card, card_filter::CardFilter, card_lock::Locked};
+
 #[derive(Component, Default)]
 pub struct CardSlot;
 
@@ -13,6 +15,17 @@ pub enum CardSlotSprite {
     Body,
 }
 
+/// where placed cards sit relative to the slot, e.g. to fan out a pile of cards
+#[derive(Component)]
+#[require(CardSlot)]
+pub struct PlacementOffset(pub Vec3);
+
+/// a dropped group is kept as a pile on this slot, any other slot only takes single cards since
+/// only the card placed on it receives [`RecievedCard`]
+#[derive(Component, Default)]
+#[require(CardSlot)]
+pub struct AcceptsStacks;
+
 #[derive(Component)]
 #[relationship(relationship_target=PlacementOfCard)]
 pub struct PlacedOnSlot(pub Entity);
@@ -21,6 +34,10 @@ pub struct PlacedOnSlot(pub Entity);
 #[relationship_target(relationship=PlacedOnSlot)]
 pub struct PlacementOfCard(Entity);
 
+/// the slot a card was placed on before its current one
+#[derive(Component)]
+pub struct PreviousSlot(pub Entity);
+
 impl PlacementOfCard {
     pub fn get(&self) -> Entity {
         self.0
@@ -32,27 +49,97 @@ pub struct CardSlotPlugin;
 #[derive(Event)]
 pub struct RecievedCard(pub Entity);
 
+/// tries to place the targeted card on the given slot, respecting locks and filters
+#[derive(Event)]
+pub struct PlaceOnSlot(pub Entity);
+
+#[derive(SystemParam)]
+pub struct CardPlacement<'w, 's> {
+    cards: Query<'w, 's, &'static Card, Without<Locked>>,
+    slots: Query<'w, 's, Option<&'static CardFilter>, (With<CardSlot>, Without<PlacementOfCard>)>,
+    stack_slots: Query<'w, 's, (), With<AcceptsStacks>>,
+    parents: Query<'w, 's, &'static ChildOf>,
+}
+
+impl CardPlacement<'_, '_> {
+    /// a card can't be placed onto itself or the pile it carries
+    fn carries(&self, card: Entity, target: Entity) -> bool {
+        target == card || self.parents.iter_ancestors(target).any(|e| e == card)
+    }
+
+    pub fn can_place(&self, card: Entity, slot: Entity) -> bool {
+        if self.carries(card, slot) {
+            return false;
+        }
+        let Ok(card) = self.cards.get(card) else {
+            return false;
+        };
+        let Ok(cf) = self.slots.get(slot) else {
+            return false;
+        };
+        cf.is_none_or(|cf| cf.check(card))
+    }
+
+    pub fn accepts_stacks(&self, slot: Entity) -> bool {
+        self.stack_slots.contains(slot)
+    }
+
+    /// whether the card can go on top of another card of a pile, which is neither a slot nor
+    /// free of placements
+    pub fn can_stack(&self, card: Entity, below: Entity) -> bool {
+        !self.carries(card, below) && self.cards.contains(card)
+    }
+}
+
 impl Plugin for CardSlotPlugin {
     fn build(&self, app: &mut App) {
-        app.add_observer(handle_placed_on_added)
+        app.add_observer(handle_place_on_slot)
+            .add_observer(handle_placed_on_added)
+            .add_observer(handle_placed_on_replaced)
             .add_observer(handle_placed_on_removed);
     }
 }
 
+fn handle_place_on_slot(
+    tr: Trigger<PlaceOnSlot>,
+    placement: CardPlacement,
+    mut commands: Commands,
+) {
+    let card = tr.target();
+    let slot = tr.0;
+    if placement.can_place(card, slot) {
+        commands.entity(card).insert(PlacedOnSlot(slot));
+    }
+}
+
 fn handle_placed_on_added(
     tr: Trigger<OnInsert, PlacedOnSlot>,
     placed_on: Query<&PlacedOnSlot>,
+    offsets: Query<&PlacementOffset>,
     mut commands: Commands,
 ) {
     let card = tr.target();
     let slot = placed_on.get(card).unwrap().0;
+    let offset = offsets.get(slot).map_or(Vec3::Z, |o| o.0);
     commands
         .entity(card)
-        .insert((ChildOf(slot), Transform::from_xyz(0.0, 0.0, 1.0)));
+        .insert((ChildOf(slot), Transform::from_translation(offset)));
 
     commands.entity(slot).trigger(RecievedCard(card));
 }
 
+fn handle_placed_on_replaced(
+    tr: Trigger<OnReplace, PlacedOnSlot>,
+    placed_on: Query<&PlacedOnSlot>,
+    mut commands: Commands,
+) {
+    let card = tr.target();
+    let slot = placed_on.get(card).unwrap().0;
+    if let Ok(mut ec) = commands.get_entity(card) {
+        ec.try_insert(PreviousSlot(slot));
+    }
+}
+
 fn handle_placed_on_removed(tr: Trigger<OnRemove, PlacedOnSlot>, mut commands: Commands) {
     let entity = tr.target();
     if let Ok(mut ec) = commands.get_entity(entity) {
@@ -0,0 +1,198 @@
+use bevy::{
+    color::palettes::css::{DARK_GRAY, WHITE},
+    prelude::*,
+};
+use enum_iterator::all;
+
+use crate::{
+    card::{Card, CardSuit, rank_symbol, suit_symbol},
+    dungeon::{Deck, Hardcore},
+    menu::GamePhase,
+    rules::{CardRole, RuleSet},
+};
+
+const CELL_SIZE: f32 = 28.0;
+
+/// shows the cards left in the deck, toggled with F2, never shown in [`Hardcore`] runs
+#[derive(Resource, Default)]
+pub struct ShowDeckInspector(pub bool);
+
+#[derive(Component)]
+struct DeckInspector;
+
+pub struct DeckInspectorPlugin;
+
+impl Plugin for DeckInspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ShowDeckInspector>()
+            .add_systems(Startup, spawn_inspector)
+            .add_systems(
+                Update,
+                (
+                    toggle_inspector.run_if(in_state(GamePhase::Running)),
+                    update_inspector,
+                )
+                    .chain(),
+            );
+    }
+}
+
+fn spawn_inspector(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Deck Inspector"),
+        DeckInspector,
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(12.0),
+            bottom: Val::Px(12.0),
+            display: Display::Grid,
+            grid_template_columns: [
+                vec![GridTrack::auto()],
+                RepeatedGridTrack::px(13, CELL_SIZE),
+                vec![GridTrack::auto()],
+            ]
+            .concat(),
+            column_gap: Val::Px(2.0),
+            row_gap: Val::Px(2.0),
+            padding: UiRect::all(Val::Px(8.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+        Visibility::Hidden,
+        GlobalZIndex(12),
+    ));
+}
+
+fn toggle_inspector(keys: Res<ButtonInput<KeyCode>>, mut show: ResMut<ShowDeckInspector>) {
+    if keys.just_pressed(KeyCode::F2) {
+        show.0 = !show.0;
+    }
+}
+
+fn role_label(role: CardRole) -> &'static str {
+    match role {
+        CardRole::Potion => "Potions",
+        CardRole::Weapon => "Weapons",
+        CardRole::Monster => "Monsters",
+        CardRole::Joker => "Jokers",
+        CardRole::Item => "Items",
+    }
+}
+
+/// what the rules make of the suit, followed by its notation, e.g. "Potions (H)"
+fn suit_label(rules: &RuleSet, suit: CardSuit) -> String {
+    format!(
+        "{} ({})",
+        role_label(rules.suit_role(suit)),
+        suit_symbol(suit)
+    )
+}
+
+fn update_inspector(
+    deck: Res<Deck>,
+    rules: Res<RuleSet>,
+    show: Res<ShowDeckInspector>,
+    hardcore: Res<Hardcore>,
+    inspector: Single<(Entity, &mut Visibility), With<DeckInspector>>,
+    mut commands: Commands,
+) {
+    if !deck.is_changed() && !show.is_changed() && !hardcore.is_changed() {
+        return;
+    }
+    let (inspector, mut visibility) = inspector.into_inner();
+
+    // not in the toggle, which doesn't run in the menu where hardcore runs are started
+    let shown = show.0 && !hardcore.0;
+    *visibility = if shown {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    if !shown {
+        return;
+    }
+
+    commands.entity(inspector).despawn_related::<Children>();
+
+    let mut cell = |text: String, color: Srgba| {
+        commands.spawn((
+            ChildOf(inspector),
+            Text::new(text),
+            TextFont::from_font_size(14.0),
+            TextColor(color.into()),
+        ));
+    };
+
+    cell(String::new(), WHITE);
+    for rank in 1..=13 {
+        cell(rank_symbol(rank), WHITE);
+    }
+    cell("Left".to_string(), WHITE);
+
+    for suit in all::<CardSuit>() {
+        let count = |rank| {
+            deck.cards
+                .iter()
+                .filter(|c| **c == Card::new(suit, rank))
+                .count()
+        };
+
+        cell(suit_label(&rules, suit), WHITE);
+        for rank in 1..=13 {
+            match count(rank) {
+                0 => cell("-".to_string(), DARK_GRAY),
+                1 => cell(rank_symbol(rank), WHITE),
+                n => cell(format!("{}x{}", n, rank_symbol(rank)), WHITE),
+            }
+        }
+        let total = deck.cards.iter().filter(|c| c.suit() == Some(suit)).count();
+        cell(total.to_string(), WHITE);
+    }
+
+    // jokers are off the grid, listed by notation in the rank columns
+    let jokers = rules.jokers();
+    if !jokers.is_empty() {
+        cell("Jokers".to_string(), WHITE);
+        for column in 0..13 {
+            match jokers.get(column).map(|effect| Card::joker(*effect)) {
+                Some(joker) if deck.cards.contains(&joker) => cell(joker.to_string(), WHITE),
+                Some(_) => cell("-".to_string(), DARK_GRAY),
+                None => cell(String::new(), WHITE),
+            }
+        }
+        let total = deck.cards.iter().filter(|c| c.is_joker()).count();
+        cell(total.to_string(), WHITE);
+    }
+
+    // custom cards are listed by name across the rank columns
+    if !rules.custom_cards() {
+        return;
+    }
+    let names: Vec<_> = deck
+        .cards
+        .iter()
+        .filter_map(|c| {
+            let definition = c.definition()?;
+            Some(format!(
+                "{} ({})",
+                definition.name,
+                role_label(rules.role(c))
+            ))
+        })
+        .collect();
+    cell("Custom".to_string(), WHITE);
+    commands.spawn((
+        ChildOf(inspector),
+        Text::new(names.join(", ")),
+        TextFont::from_font_size(14.0),
+        Node {
+            grid_column: GridPlacement::span(13),
+            ..default()
+        },
+    ));
+    commands.spawn((
+        ChildOf(inspector),
+        Text::new(names.len().to_string()),
+        TextFont::from_font_size(14.0),
+    ));
+}
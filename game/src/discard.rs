@@ -0,0 +1,149 @@
+use bevy::prelude::*;
+
+use crate::{
+    assets::AssetHandles,
+    card::Card,
+    card_drag_drop::SnapRadius,
+    card_filter::CardFilter,
+    card_lock::{LockCard, LocksPlacedCards},
+    card_quick_play::QuickPlayTarget,
+    card_slot::{CardSlot, PlacedOnSlot},
+    menu::{GamePhase, game_running},
+};
+
+/// offset between cards of the pile so its height shows
+const PILE_STEP: Vec3 = Vec3::new(0.5, 0.5, 0.01);
+const VIEWER_CARD_SIZE: Vec2 = Vec2::new(72.5, 100.0);
+
+/// where resolved cards end up, clicking it fans out every discarded card
+#[derive(Component)]
+#[require(CardSlot, CardFilter)]
+pub struct DiscardPile;
+
+#[derive(Component)]
+#[relationship(relationship_target=DiscardedCards)]
+pub struct InDiscardPile(pub Entity);
+
+/// in the order they were discarded
+#[derive(Component)]
+#[relationship_target(relationship=InDiscardPile)]
+pub struct DiscardedCards(Vec<Entity>);
+
+impl DiscardedCards {
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.0.iter().copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// moves the targeted card onto the discard pile, it's no longer playable nor a slot
+#[derive(Event)]
+pub struct Discard;
+
+#[derive(Component)]
+struct DiscardViewer;
+
+pub struct DiscardPlugin;
+
+impl Plugin for DiscardPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(handle_discard)
+            .add_observer(handle_pile_click);
+    }
+}
+
+fn handle_discard(
+    tr: Trigger<Discard>,
+    piles: Query<(Entity, Option<&DiscardedCards>), With<DiscardPile>>,
+    mut commands: Commands,
+) {
+    let card = tr.target();
+    let Ok((pile, discarded)) = piles.single() else {
+        return;
+    };
+    let height = discarded.map_or(0, |d| d.len()) as f32;
+
+    commands
+        .entity(card)
+        .remove::<(
+            PlacedOnSlot,
+            CardSlot,
+            CardFilter,
+            SnapRadius,
+            QuickPlayTarget,
+            LocksPlacedCards,
+        )>()
+        .insert((
+            InDiscardPile(pile),
+            ChildOf(pile),
+            Transform::from_translation(Vec3::Z + PILE_STEP * height),
+        ))
+        .trigger(LockCard);
+}
+
+fn handle_pile_click(
+    mut tr: Trigger<Pointer<Click>>,
+    piles: Query<Option<&DiscardedCards>, With<DiscardPile>>,
+    cards: Query<&Card>,
+    viewers: Query<(), With<DiscardViewer>>,
+    assets: Res<AssetHandles>,
+    phase: Option<Res<State<GamePhase>>>,
+    mut commands: Commands,
+) {
+    let Ok(discarded) = piles.get(tr.target()) else {
+        return;
+    };
+    tr.propagate(false);
+    if tr.button != PointerButton::Primary || !viewers.is_empty() || !game_running(phase) {
+        return;
+    }
+
+    let viewer = commands
+        .spawn((
+            Name::new("Discard Viewer"),
+            DiscardViewer,
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_wrap: FlexWrap::Wrap,
+                align_content: AlignContent::Center,
+                justify_content: JustifyContent::Center,
+                column_gap: Val::Px(6.0),
+                row_gap: Val::Px(6.0),
+                padding: UiRect::all(Val::Px(40.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+            GlobalZIndex(15),
+        ))
+        .observe(close_viewer)
+        .id();
+
+    for card in discarded.into_iter().flat_map(|d| d.iter()) {
+        let Ok(card) = cards.get(card) else {
+            continue;
+        };
+        commands.spawn((
+            ChildOf(viewer),
+            ImageNode::new(assets.get_card_image(card)),
+            Node {
+                width: Val::Px(VIEWER_CARD_SIZE.x),
+                height: Val::Px(VIEWER_CARD_SIZE.y),
+                ..default()
+            },
+            Pickable::IGNORE,
+        ));
+    }
+}
+
+fn close_viewer(tr: Trigger<Pointer<Click>>, mut commands: Commands) {
+    commands.entity(tr.target()).despawn();
+}
@@ -0,0 +1,307 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+
+use crate::{
+    card::Card,
+    card_definition::CustomCards,
+    card_slot::{CardSlot, PlacedOnSlot, PlacementOfCard},
+    menu::GamePhase,
+    rules::RuleSet,
+    status_effect::GameTick,
+};
+
+/// cards of the deck are dealt into these whenever a new room is entered
+#[derive(Component)]
+#[require(CardSlot)]
+pub struct RoomSlot;
+
+/// the slot holding the equipped weapon
+#[derive(Component)]
+#[require(CardSlot)]
+pub struct WeaponSlot;
+
+/// top level entity of the current run, despawned when a new run starts
+#[derive(Component, Default)]
+pub struct RunScoped;
+
+/// ends the current run and deals a new dungeon from a fresh seed, unless one was given with
+/// `--seed`
+#[derive(Event)]
+pub struct NewRun;
+
+/// triggered once the previous run is cleared, the scene of the new run is spawned on it
+#[derive(Event)]
+pub struct RunStarted;
+
+/// seed of the current run, the order of the deck follows from it
+#[derive(Resource, Clone, Copy)]
+pub struct Seed(pub u64);
+
+impl Seed {
+    pub fn from_time() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos());
+        Self(nanos as u64)
+    }
+
+    /// the seed passed as `--seed <number>`, every run then deals the same dungeon
+    pub fn from_args() -> Option<Self> {
+        let mut args = std::env::args().skip_while(|arg| arg != "--seed").skip(1);
+        let seed = args.next()?;
+        seed.parse()
+            .map(Self)
+            .inspect_err(|err| error!("invalid seed '{}': {}", seed, err))
+            .ok()
+    }
+}
+
+/// the draw pile, cards are drawn from the back
+#[derive(Resource, Default)]
+pub struct Deck {
+    pub cards: Vec<Card>,
+    /// number of cards the run started with
+    size: usize,
+}
+
+impl Deck {
+    pub fn shuffled(mut cards: Vec<Card>, seed: Seed) -> Self {
+        let mut rng = SplitMix64(seed.0);
+        for i in (1..cards.len()).rev() {
+            let j = (rng.next() % (i as u64 + 1)) as usize;
+            cards.swap(i, j);
+        }
+
+        Self {
+            size: cards.len(),
+            cards,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+}
+
+/// number of the current room, starting at 1 once the first room is dealt
+#[derive(Resource, Default)]
+pub struct Room(pub u32);
+
+#[derive(Resource, Default, PartialEq)]
+pub struct Score(pub i32);
+
+/// triggered whenever the [`Score`] takes a new value
+#[derive(Event)]
+pub struct ScoreChanged;
+
+/// what happened in the current room so far
+#[derive(Resource, Default)]
+pub struct RoomState {
+    /// cards played in this room
+    pub played: u32,
+    /// potions that healed in this room
+    pub potions: u32,
+    /// whether the previous room was avoided
+    pub avoided: bool,
+    avoiding: bool,
+}
+
+/// puts the cards of the current room at the bottom of the deck and deals a new one, if the
+/// rules allow it
+#[derive(Event)]
+pub struct AvoidRoom;
+
+/// deals up to this many cards from the deck into the empty room slots, within the current room
+#[derive(Event)]
+pub struct DrawCards(pub u32);
+
+/// hides every aid revealing what is left in the deck
+#[derive(Resource, Default)]
+pub struct Hardcore(pub bool);
+
+/// small deterministic generator so a seed always deals the same dungeon
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+}
+
+pub struct DungeonPlugin;
+
+impl Plugin for DungeonPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Seed::from_time())
+            .init_resource::<Deck>()
+            .init_resource::<Room>()
+            .init_resource::<RoomState>()
+            .init_resource::<Score>()
+            .init_resource::<Hardcore>()
+            .add_observer(handle_new_run)
+            .add_observer(handle_avoid_room)
+            .add_observer(handle_draw_cards)
+            .add_observer(count_played_cards)
+            .add_systems(
+                Update,
+                (avoid_on_key.run_if(in_state(GamePhase::Running)), deal_room),
+            );
+    }
+}
+
+fn deal_room(
+    slots: Query<(Entity, Has<PlacementOfCard>), With<RoomSlot>>,
+    rules: Res<RuleSet>,
+    mut deck: ResMut<Deck>,
+    mut room: ResMut<Room>,
+    mut room_state: ResMut<RoomState>,
+    mut commands: Commands,
+) {
+    let occupied = slots.iter().filter(|(_, occupied)| *occupied).count();
+    if slots.is_empty() || occupied > rules.room_carry_over() || deck.is_empty() {
+        return;
+    }
+
+    for (slot, occupied) in &slots {
+        if occupied {
+            continue;
+        }
+        let Some(card) = deck.cards.pop() else {
+            break;
+        };
+        spawn_room_card(&mut commands, card, slot);
+    }
+
+    room.0 += 1;
+    *room_state = RoomState {
+        avoided: room_state.avoiding,
+        ..default()
+    };
+    commands.trigger(GameTick::RoomEntered);
+}
+
+fn spawn_room_card(commands: &mut Commands, card: Card, slot: Entity) {
+    commands.spawn((
+        Name::new(format!("Card {}", card)),
+        card,
+        Pickable::default(),
+        PlacedOnSlot(slot),
+    ));
+}
+
+fn handle_draw_cards(
+    tr: Trigger<DrawCards>,
+    slots: Query<Entity, (With<RoomSlot>, Without<PlacementOfCard>)>,
+    mut deck: ResMut<Deck>,
+    mut commands: Commands,
+) {
+    for slot in slots.iter().take(tr.0 as usize) {
+        let Some(card) = deck.cards.pop() else {
+            break;
+        };
+        spawn_room_card(&mut commands, card, slot);
+    }
+}
+
+fn handle_new_run(
+    _: Trigger<NewRun>,
+    scoped: Query<Entity, With<RunScoped>>,
+    rules: Res<RuleSet>,
+    custom_cards: CustomCards,
+    mut commands: Commands,
+) {
+    for entity in &scoped {
+        commands.entity(entity).despawn();
+    }
+
+    let seed = Seed::from_args().unwrap_or_else(Seed::from_time);
+    commands.insert_resource(seed);
+    let mut cards = rules.deck();
+    if rules.custom_cards() {
+        cards.extend(custom_cards.cards());
+    }
+    commands.insert_resource(Deck::shuffled(cards, seed));
+    commands.insert_resource(Room::default());
+    commands.insert_resource(RoomState::default());
+    commands.insert_resource(rules.combat_mode());
+    commands.insert_resource(Hardcore(rules.hardcore()));
+    commands.insert_resource(Score::default());
+    commands.trigger(RunStarted);
+}
+
+fn count_played_cards(tr: Trigger<GameTick>, mut room_state: ResMut<RoomState>) {
+    if *tr.event() == GameTick::CardPlayed {
+        room_state.played += 1;
+    }
+}
+
+fn avoid_on_key(keys: Res<ButtonInput<KeyCode>>, mut commands: Commands) {
+    if keys.just_pressed(KeyCode::KeyA) {
+        commands.trigger(AvoidRoom);
+    }
+}
+
+fn handle_avoid_room(
+    _: Trigger<AvoidRoom>,
+    slots: Query<&PlacementOfCard, With<RoomSlot>>,
+    cards: Query<&Card>,
+    rules: Res<RuleSet>,
+    mut room_state: ResMut<RoomState>,
+    mut deck: ResMut<Deck>,
+    mut commands: Commands,
+) {
+    if !rules.can_avoid(&room_state) {
+        return;
+    }
+
+    for placed in &slots {
+        let Ok(card) = cards.get(placed.get()) else {
+            continue;
+        };
+        deck.cards.insert(0, card.clone());
+        commands.entity(placed.get()).despawn();
+    }
+    room_state.avoiding = true;
+}
+
+#[cfg(test)]
+mod tests {
+    use enum_iterator::cardinality;
+
+    use super::*;
+    use crate::{card::JokerEffect, rules::RulesPreset};
+
+    fn deck(preset: RulesPreset, seed: u64) -> Deck {
+        Deck::shuffled(RuleSet::from(preset).deck(), Seed(seed))
+    }
+
+    #[test]
+    fn same_seed_deals_the_same_order() {
+        assert!(deck(RulesPreset::Classic, 42).cards == deck(RulesPreset::Classic, 42).cards);
+        assert!(deck(RulesPreset::Classic, 42).cards != deck(RulesPreset::Classic, 43).cards);
+    }
+
+    #[test]
+    fn deck_size_follows_the_rules() {
+        // 26 black cards and the 18 red ones from 2 to 10
+        let classic = deck(RulesPreset::Classic, 7);
+        assert_eq!(classic.len(), 44);
+        assert_eq!(classic.size(), 44);
+
+        let jokers = deck(RulesPreset::Jokers, 7);
+        assert_eq!(jokers.len(), 44 + cardinality::<JokerEffect>());
+    }
+}
@@ -0,0 +1,504 @@
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    ecs::system::SystemParam,
+    prelude::*,
+};
+use serde::Deserialize;
+
+use crate::{
+    card::{Card, JokerEffect},
+    card_definition::{CardCategory, DataFileError},
+    card_drag_drop::SnapRadius,
+    card_filter::CardFilter,
+    card_lock::LocksPlacedCards,
+    card_quick_play::QuickPlayTarget,
+    card_slot::{PlacedOnSlot, PlacementOfCard, PreviousSlot, RecievedCard},
+    discard::Discard,
+    dungeon::{DrawCards, RoomSlot, RoomState},
+    health::{Damage, DamageKind, Heal, Health, Shield},
+    monster::Monster,
+    player::{DoubleNextPotion, Player, PlayerWeapon},
+    rules::{CardRole, RuleSet},
+    status_effect::{ApplyEffect, GameTick, StatusEffect, Stunned},
+    validation::ReportConfigError,
+};
+
+/// a primitive of the effect language, a script is a list of them run in order on the played card
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize)]
+pub enum Effect {
+    /// heals the player
+    Heal(Amount),
+    /// hurts the target, with the suit of the played card
+    Damage(Target, Amount),
+    /// deals cards from the deck into the empty room slots
+    Draw(u32),
+    /// adds to the player's shield
+    Shield(Amount),
+    /// moves the target to the discard pile, [`Target::Player`] is skipped
+    Discard(Target),
+    /// afflicts the target with a status effect, stacking with an active one of the same kind
+    Apply(Target, StatusEffect),
+    /// moves the played card onto the weapon slot, replacing the equipped weapon, the rest of the
+    /// script is skipped as the card is resolved again once it lands there
+    Equip,
+    /// the played card becomes the weapon monsters are fought on
+    Arm,
+    /// the equipped weapon hits the played card for its block, only monsters with health take it
+    Strike,
+    /// the equipped weapon can only take monsters weaker than the played card from now on, jokers
+    /// don't count
+    Cap,
+    /// sends the played card back to the slot it came from
+    Return,
+    /// counts the played card towards the potions of the room
+    CountPotion,
+    /// the next potion that heals doubles its heal
+    BoostPotion,
+    /// uses up [`Effect::BoostPotion`]
+    UseBoost,
+    /// runs the effects only if the condition holds when reached
+    If(Condition, Vec<Effect>),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize)]
+pub enum Amount {
+    /// the value of the played card, a deep combat monster counts what is left of its health
+    Value,
+    /// the value multiplied
+    Times(u32),
+    Fixed(u32),
+    /// the value minus the block of the equipped weapon, nothing is blocked while stunned
+    Blocked,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize)]
+pub enum Target {
+    Player,
+    /// the played card
+    This,
+    /// every card left in the room
+    Room,
+    /// up to this many cards left in the room
+    RoomCards(u32),
+}
+
+/// what an [`Effect::If`] can check, there is no comparison of values nor `And`/`Or` yet
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize)]
+pub enum Condition {
+    /// the room didn't reach its potion limit yet
+    PotionAllowed,
+    /// an [`Effect::BoostPotion`] is waiting to be used
+    Boosted,
+    /// the played card is a monster with more health than the equipped weapon blocks
+    Survives,
+    Not(Box<Condition>),
+}
+
+/// loaded from a `.effect.ron` file holding a list of [`Effect`]s
+#[derive(Asset, TypePath, Clone, Debug)]
+pub struct EffectScript(pub Vec<Effect>);
+
+#[derive(Default)]
+struct EffectScriptLoader;
+
+impl AssetLoader for EffectScriptLoader {
+    type Asset = EffectScript;
+    type Settings = ();
+    type Error = DataFileError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _: &(),
+        _: &mut LoadContext<'_>,
+    ) -> Result<EffectScript, DataFileError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(EffectScript(ron::de::from_bytes(&bytes)?))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["effect.ron"]
+    }
+}
+
+/// where a card was played, which picks the script it runs
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PlayedOn {
+    /// the script of the card's role
+    Body,
+    /// equipping it
+    WeaponSlot,
+    /// fighting it with the equipped weapon
+    Weapon,
+}
+
+/// the scripts of the standard cards, custom cards run theirs after the one of their category
+#[derive(Resource)]
+pub struct RoleScripts {
+    potion: Handle<EffectScript>,
+    weapon: Handle<EffectScript>,
+    monster: Handle<EffectScript>,
+    item: Handle<EffectScript>,
+    clear_room: Handle<EffectScript>,
+    double_potion: Handle<EffectScript>,
+    equip: Handle<EffectScript>,
+    fight: Handle<EffectScript>,
+}
+
+impl RoleScripts {
+    fn load(server: &AssetServer) -> Self {
+        Self {
+            potion: server.load("effects/potion.effect.ron"),
+            weapon: server.load("effects/weapon.effect.ron"),
+            monster: server.load("effects/monster.effect.ron"),
+            item: server.load("effects/item.effect.ron"),
+            clear_room: server.load("effects/clear_room.effect.ron"),
+            double_potion: server.load("effects/double_potion.effect.ron"),
+            equip: server.load("effects/equip.effect.ron"),
+            fight: server.load("effects/fight.effect.ron"),
+        }
+    }
+
+    fn get(&self, played_on: PlayedOn, role: CardRole, card: &Card) -> &Handle<EffectScript> {
+        match (played_on, role, card.joker_effect()) {
+            (PlayedOn::WeaponSlot, ..) => &self.equip,
+            (PlayedOn::Weapon, ..) => &self.fight,
+            (PlayedOn::Body, CardRole::Potion, _) => &self.potion,
+            (PlayedOn::Body, CardRole::Weapon, _) => &self.weapon,
+            (PlayedOn::Body, CardRole::Monster, _) => &self.monster,
+            (PlayedOn::Body, CardRole::Joker, Some(JokerEffect::ClearRoom)) => &self.clear_room,
+            (PlayedOn::Body, CardRole::Joker, Some(JokerEffect::DoublePotion)) => {
+                &self.double_potion
+            }
+            (PlayedOn::Body, CardRole::Joker | CardRole::Item, _) => &self.item,
+        }
+    }
+}
+
+/// the effects a card runs when played
+#[derive(SystemParam)]
+pub struct CardScripts<'w> {
+    role_scripts: Res<'w, RoleScripts>,
+    scripts: Res<'w, Assets<EffectScript>>,
+    rules: Res<'w, RuleSet>,
+}
+
+impl CardScripts<'_> {
+    /// the script of where the card was played followed by the effects of its definition, `None`
+    /// if the script is missing or invalid, see [`report_missing_script`]
+    pub fn effects(&self, card: &Card, played_on: PlayedOn) -> Option<Vec<Effect>> {
+        let role = self.rules.role(card);
+        let script = self
+            .scripts
+            .get(self.role_scripts.get(played_on, role, card))?;
+        let mut effects = script.0.clone();
+        if let Some(definition) = card.definition() {
+            effects.extend(definition.effects.iter().cloned());
+        }
+        Some(effects)
+    }
+}
+
+/// without its script the card can't be played, the caller decides where it goes instead
+pub fn report_missing_script(commands: &mut Commands, entity: Entity, card: &Card) {
+    commands.trigger(ReportConfigError {
+        entity,
+        message: format!("effect script of {} is missing or invalid", card),
+    });
+}
+
+/// the weapon that takes any monster, before it slays one
+fn weapon_filter(rules: &RuleSet) -> CardFilter {
+    CardFilter::empty()
+        .with_suit(rules.suits(CardRole::Monster))
+        .with_jokers([JokerEffect::AnyMonster])
+        .with_categories([CardCategory::Monster])
+        .with_ace_rank(rules.ace_value())
+}
+
+/// runs the effects on the targeted card, ends the turn unless the card was equipped
+#[derive(Event)]
+pub struct RunEffects(pub Vec<Effect>);
+
+pub struct EffectPlugin;
+
+impl Plugin for EffectPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<EffectScript>()
+            .register_asset_loader(EffectScriptLoader)
+            .add_systems(PreStartup, load_role_scripts)
+            .add_observer(run_effects);
+    }
+}
+
+fn load_role_scripts(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(RoleScripts::load(&asset_server));
+}
+
+/// a monster placed on the equipped weapon fights it, it goes back if that can't be resolved
+fn handle_card_on_weapon(
+    tr: Trigger<RecievedCard>,
+    cards: Query<&Card>,
+    previous_slots: Query<&PreviousSlot>,
+    card_scripts: CardScripts,
+    mut commands: Commands,
+) {
+    let monster_e = tr.0;
+    let monster = cards.get(monster_e).unwrap();
+
+    let Some(effects) = card_scripts.effects(monster, PlayedOn::Weapon) else {
+        report_missing_script(&mut commands, monster_e, monster);
+        if let Ok(prev) = previous_slots.get(monster_e) {
+            commands.entity(monster_e).insert(PlacedOnSlot(prev.0));
+        }
+        return;
+    };
+    commands.entity(monster_e).trigger(RunEffects(effects));
+}
+
+#[derive(SystemParam)]
+struct EffectContext<'w, 's> {
+    cards: Query<'w, 's, &'static Card>,
+    monsters: Query<'w, 's, &'static Health, With<Monster>>,
+    placements: Query<'w, 's, &'static PlacementOfCard>,
+    previous_slots: Query<'w, 's, &'static PreviousSlot>,
+    room_slots: Query<'w, 's, &'static PlacementOfCard, With<RoomSlot>>,
+    rules: Res<'w, RuleSet>,
+    room_state: ResMut<'w, RoomState>,
+    commands: Commands<'w, 's>,
+}
+
+/// what a script changed so far, commands only apply once it's done
+struct Run {
+    card: Entity,
+    player: Entity,
+    weapon_slot: Entity,
+    /// the card on the weapon slot
+    weapon: Option<Entity>,
+    stunned: bool,
+    boosted: bool,
+    shield: u32,
+    equipped: bool,
+    discarded: Vec<Entity>,
+}
+
+#[allow(clippy::type_complexity)]
+fn run_effects(
+    tr: Trigger<RunEffects>,
+    player: Query<
+        (
+            Entity,
+            &PlayerWeapon,
+            Has<Stunned>,
+            Has<DoubleNextPotion>,
+            Option<&Shield>,
+        ),
+        With<Player>,
+    >,
+    mut ctx: EffectContext,
+) {
+    let Ok((player, weapon_slot, stunned, boosted, shield)) = player.single() else {
+        return;
+    };
+    let mut run = Run {
+        card: tr.target(),
+        player,
+        weapon_slot: weapon_slot.0,
+        weapon: ctx.placements.get(weapon_slot.0).ok().map(|p| p.get()),
+        stunned,
+        boosted,
+        shield: shield.map_or(0, |s| s.0),
+        equipped: false,
+        discarded: Vec::new(),
+    };
+
+    ctx.run(&mut run, &tr.0);
+
+    if !run.equipped {
+        ctx.commands.trigger(GameTick::CardPlayed);
+    }
+}
+
+impl EffectContext<'_, '_> {
+    fn run(&mut self, run: &mut Run, effects: &[Effect]) {
+        for effect in effects {
+            if run.equipped {
+                return;
+            }
+            match effect {
+                Effect::Heal(amount) => {
+                    let amount = self.amount(run, *amount);
+                    self.commands.entity(run.player).trigger(Heal {
+                        amount,
+                        source: Some(run.card),
+                    });
+                }
+                Effect::Damage(target, amount) => {
+                    let amount = self.amount(run, *amount);
+                    let kind = self
+                        .cards
+                        .get(run.card)
+                        .map_or(DamageKind::True, DamageKind::from);
+                    for target in self.targets(run, *target) {
+                        // only the player and monsters can be hurt
+                        if target != run.player && !self.monsters.contains(target) {
+                            continue;
+                        }
+                        self.commands.entity(target).trigger(Damage {
+                            amount,
+                            kind,
+                            source: Some(run.card),
+                        });
+                    }
+                }
+                Effect::Apply(target, effect) => {
+                    for target in self.targets(run, *target) {
+                        // only the player and monsters tick their effects
+                        if target != run.player && !self.monsters.contains(target) {
+                            continue;
+                        }
+                        self.commands.entity(target).trigger(ApplyEffect(*effect));
+                    }
+                }
+                Effect::Draw(count) => self.commands.trigger(DrawCards(*count)),
+                Effect::Shield(amount) => {
+                    run.shield += self.amount(run, *amount);
+                    self.commands.entity(run.player).insert(Shield(run.shield));
+                }
+                Effect::Discard(target) => {
+                    for target in self.targets(run, *target) {
+                        if target == run.player {
+                            continue;
+                        }
+                        run.discarded.push(target);
+                        self.commands.entity(target).trigger(Discard);
+                    }
+                }
+                Effect::Equip => {
+                    if let Ok(equipped) = self.placements.get(run.weapon_slot) {
+                        self.commands.entity(equipped.get()).trigger(Discard);
+                    }
+                    self.commands
+                        .entity(run.card)
+                        .insert(PlacedOnSlot(run.weapon_slot));
+                    run.equipped = true;
+                }
+                Effect::Arm => {
+                    self.commands
+                        .entity(run.card)
+                        .insert((
+                            weapon_filter(&self.rules),
+                            LocksPlacedCards,
+                            SnapRadius(120.0),
+                            QuickPlayTarget(1),
+                        ))
+                        .observe(handle_card_on_weapon);
+                }
+                Effect::Strike => {
+                    let Some(weapon) = run.weapon else {
+                        continue;
+                    };
+                    if self.monsters.contains(run.card) {
+                        let kind = self
+                            .cards
+                            .get(weapon)
+                            .map_or(DamageKind::True, DamageKind::from);
+                        let amount = self.block(run);
+                        self.commands.entity(run.card).trigger(Damage {
+                            amount,
+                            kind,
+                            source: Some(weapon),
+                        });
+                    }
+                }
+                Effect::Cap => {
+                    let (Some(weapon), Ok(card)) = (run.weapon, self.cards.get(run.card)) else {
+                        continue;
+                    };
+                    if card.joker_effect().is_some() {
+                        continue;
+                    }
+                    let value = self.rules.value(card);
+                    self.commands
+                        .entity(weapon)
+                        .insert(weapon_filter(&self.rules).with_max_rank(value.saturating_sub(1)));
+                }
+                Effect::Return => {
+                    if let Ok(prev) = self.previous_slots.get(run.card) {
+                        self.commands.entity(run.card).insert(PlacedOnSlot(prev.0));
+                    }
+                }
+                Effect::CountPotion => self.room_state.potions += 1,
+                Effect::BoostPotion => {
+                    run.boosted = true;
+                    self.commands.entity(run.player).insert(DoubleNextPotion);
+                }
+                Effect::UseBoost => {
+                    run.boosted = false;
+                    self.commands
+                        .entity(run.player)
+                        .remove::<DoubleNextPotion>();
+                }
+                Effect::If(condition, effects) => {
+                    if self.check(run, condition) {
+                        self.run(run, effects);
+                    }
+                }
+            }
+        }
+    }
+
+    fn amount(&self, run: &Run, amount: Amount) -> u32 {
+        let value = || match self.monsters.get(run.card) {
+            Ok(health) => health.0,
+            Err(_) => self.cards.get(run.card).map_or(0, |c| self.rules.value(c)),
+        };
+
+        match amount {
+            Amount::Value => value(),
+            Amount::Times(factor) => value() * factor,
+            Amount::Fixed(amount) => amount,
+            Amount::Blocked => value().saturating_sub(self.block(run)),
+        }
+    }
+
+    fn block(&self, run: &Run) -> u32 {
+        if run.stunned {
+            return 0;
+        }
+        run.weapon
+            .and_then(|weapon| self.cards.get(weapon).ok())
+            .map_or(0, |weapon| self.rules.value(weapon))
+    }
+
+    fn targets(&self, run: &Run, target: Target) -> Vec<Entity> {
+        let room = self
+            .room_slots
+            .iter()
+            .map(|placement| placement.get())
+            .filter(|card| !run.discarded.contains(card));
+
+        match target {
+            Target::Player => vec![run.player],
+            Target::This => vec![run.card],
+            Target::Room => room.collect(),
+            Target::RoomCards(count) => room.take(count as usize).collect(),
+        }
+    }
+
+    fn check(&self, run: &Run, condition: &Condition) -> bool {
+        match condition {
+            Condition::PotionAllowed => self
+                .rules
+                .potions_per_room()
+                .is_none_or(|limit| self.room_state.potions < limit),
+            Condition::Boosted => run.boosted,
+            Condition::Survives => self
+                .monsters
+                .get(run.card)
+                .is_ok_and(|health| health.0 > self.block(run)),
+            Condition::Not(condition) => !self.check(run, condition),
+        }
+    }
+}
@@ -2,47 +2,363 @@ use std::cmp::{max, min};
 
 use bevy::prelude::*;
 
-use crate::status_bar::{GetValue, IntoStatusBar};
+use crate::{
+    card::{Card, CardSuit},
+    status_bar::{GetValue, IntoStatusBar},
+    validation::ReportConfigError,
+};
 
 #[derive(Component)]
 #[component(immutable)]
 pub struct MaxHealth(pub u32);
 
+/// must be paired with a [`MaxHealth`], entities lacking one are reported as misconfigured
 #[derive(Component)]
-#[require(MaxHealth = enforce_exists!(MaxHealth))]
 #[component(immutable)]
 pub struct Health(pub u32);
 
+/// absorbs incoming damage before anything else and is used up in the process
+#[derive(Component)]
+#[component(immutable)]
+pub struct Shield(pub u32);
+
+/// reduces every hit by a flat amount
+#[derive(Component)]
+pub struct Armor(pub u32);
+
+/// reduces hits of a given suit by a flat amount
+#[derive(Component, Default)]
+pub struct Resistances([u32; 4]);
+
+impl Resistances {
+    pub fn with(mut self, suit: CardSuit, amount: u32) -> Self {
+        self.0[suit as usize] = amount;
+        self
+    }
+
+    pub fn get(&self, suit: CardSuit) -> u32 {
+        self.0[suit as usize]
+    }
+}
+
+/// healing above [`MaxHealth`] is kept as [`TemporaryHealth`] up to this cap
+#[derive(Component)]
+pub struct Overheal(pub u32);
+
+/// lost before [`Health`] when taking damage
+#[derive(Component)]
+#[component(immutable)]
+pub struct TemporaryHealth(pub u32);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum DamageKind {
+    /// bypasses armor and resistances
+    True,
+    Suit(CardSuit),
+}
+
+/// jokers have no suit and deal true damage
+impl From<&Card> for DamageKind {
+    fn from(card: &Card) -> Self {
+        card.suit().map_or(DamageKind::True, DamageKind::Suit)
+    }
+}
+
+/// damage passes through shield, armor, resistance and temporary health in that order
+#[derive(Event)]
+pub struct Damage {
+    pub amount: u32,
+    pub kind: DamageKind,
+    pub source: Option<Entity>,
+}
+
+#[derive(Event)]
+pub struct Heal {
+    pub amount: u32,
+    pub source: Option<Entity>,
+}
+
+/// signed shorthand for an untyped [`Heal`] or [`Damage`]
 #[derive(Event)]
 pub struct AdjustHealth(pub i32);
 
+/// like [`AdjustHealth`] but as a fraction of [`MaxHealth`], e.g. `0.5` heals half
+#[derive(Event)]
+pub struct AdjustHealthPercent(pub f32);
+
+/// what happens to the current [`Health`] when [`MaxHealth`] changes
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum MaxHealthPolicy {
+    /// health stays the same, clamped to the new maximum
+    #[default]
+    Keep,
+    /// health keeps its fraction of the maximum
+    Scale,
+    /// health is set to the new maximum
+    Fill,
+}
+
+#[derive(Event)]
+pub struct AdjustMaxHealth {
+    pub amount: i32,
+    pub policy: MaxHealthPolicy,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HealthError {
+    MissingEntity,
+    MissingMaxHealth,
+}
+
+/// triggered on the target of a [`Heal`], [`Damage`] or [`AdjustHealth`] that can't have health
+#[derive(Event)]
+pub struct AdjustHealthFailed(pub HealthError);
+
+/// triggered on an entity whose [`Health`] went down
+#[derive(Event)]
+pub struct Damaged {
+    pub amount: u32,
+    pub prev: u32,
+    pub new: u32,
+}
+
+/// triggered on an entity whose [`Health`] went up
+#[derive(Event)]
+pub struct Healed {
+    pub amount: u32,
+    pub prev: u32,
+    pub new: u32,
+}
+
+/// triggered on an entity that lost its last [`Health`]
+#[derive(Event)]
+pub struct Died;
+
+/// triggered on a dead entity that regained [`Health`]
+#[derive(Event)]
+pub struct Revived;
+
 pub struct HealthPlugin;
 
 impl Plugin for HealthPlugin {
     fn build(&self, app: &mut App) {
-        app.add_observer(handle_adjust_health);
+        app.add_observer(validate_health)
+            .add_observer(handle_adjust_health)
+            .add_observer(handle_adjust_health_percent)
+            .add_observer(handle_adjust_max_health)
+            .add_observer(handle_damage)
+            .add_observer(handle_heal);
+    }
+}
+
+fn reject(commands: &mut Commands, entity: Entity, error: HealthError) {
+    let message = match error {
+        HealthError::MissingEntity => "adjusted health of a missing entity",
+        HealthError::MissingMaxHealth => "has or gained health without MaxHealth",
+    };
+    commands.trigger(ReportConfigError {
+        entity,
+        message: message.to_string(),
+    });
+    commands.trigger_targets(AdjustHealthFailed(error), entity);
+}
+
+fn validate_health(
+    tr: Trigger<OnInsert, Health>,
+    max_health: Query<(), With<MaxHealth>>,
+    mut commands: Commands,
+) {
+    let entity = tr.target();
+    if !max_health.contains(entity) {
+        commands.trigger(ReportConfigError {
+            entity,
+            message: "Health requires MaxHealth".to_string(),
+        });
+    }
+}
+
+fn handle_adjust_health(tr: Trigger<AdjustHealth>, mut commands: Commands) {
+    let mut ec = commands.entity(tr.target());
+    match tr.0 {
+        0 => {}
+        amount if amount > 0 => {
+            ec.trigger(Heal {
+                amount: amount as u32,
+                source: None,
+            });
+        }
+        amount => {
+            ec.trigger(Damage {
+                amount: amount.unsigned_abs(),
+                kind: DamageKind::True,
+                source: None,
+            });
+        }
+    }
+}
+
+fn handle_adjust_health_percent(
+    tr: Trigger<AdjustHealthPercent>,
+    max_health: Query<&MaxHealth>,
+    mut commands: Commands,
+) {
+    let entity = tr.target();
+    let Ok(max_health) = max_health.get(entity) else {
+        return reject(&mut commands, entity, HealthError::MissingMaxHealth);
+    };
+
+    let amount = (max_health.0 as f32 * tr.0).round() as i32;
+    commands.entity(entity).trigger(AdjustHealth(amount));
+}
+
+fn handle_adjust_max_health(
+    tr: Trigger<AdjustMaxHealth>,
+    targets: Query<(Option<&Health>, Option<&MaxHealth>)>,
+    mut commands: Commands,
+) {
+    let entity = tr.target();
+    let Ok((health, max_health)) = targets.get(entity) else {
+        return reject(&mut commands, entity, HealthError::MissingEntity);
+    };
+    let Some(max_health) = max_health else {
+        return reject(&mut commands, entity, HealthError::MissingMaxHealth);
+    };
+
+    let prev = health.map_or(0, |h| h.0);
+    let new_max = max(1, max_health.0 as i32 + tr.amount) as u32;
+    let new = match tr.policy {
+        MaxHealthPolicy::Keep => min(prev, new_max),
+        // a living entity isn't killed by rounding down
+        MaxHealthPolicy::Scale if prev > 0 => max(
+            1,
+            (prev as f32 * new_max as f32 / max_health.0 as f32).round() as u32,
+        ),
+        MaxHealthPolicy::Scale => 0,
+        MaxHealthPolicy::Fill => new_max,
+    };
+
+    let mut ec = commands.entity(entity);
+    ec.insert(MaxHealth(new_max));
+    set_health(&mut ec, prev, new);
+}
+
+/// applies a health change and triggers the matching events
+fn set_health(ec: &mut EntityCommands, prev: u32, new: u32) {
+    match new {
+        0 => ec.remove::<Health>(),
+        new => ec.insert(Health(new)),
+    };
+
+    if new < prev {
+        ec.trigger(Damaged {
+            amount: prev - new,
+            prev,
+            new,
+        });
+        if new == 0 {
+            ec.trigger(Died);
+        }
+    } else if new > prev {
+        ec.trigger(Healed {
+            amount: new - prev,
+            prev,
+            new,
+        });
+        if prev == 0 {
+            ec.trigger(Revived);
+        }
     }
 }
 
-fn handle_adjust_health(
-    tr: Trigger<AdjustHealth>,
-    health: Query<(Option<&Health>, &MaxHealth)>,
+/// takes up to `amount` from `pool`, returning what is left of the damage
+fn absorb(amount: u32, pool: &mut u32) -> u32 {
+    let absorbed = min(amount, *pool);
+    *pool -= absorbed;
+    amount - absorbed
+}
+
+#[allow(clippy::type_complexity)]
+fn handle_damage(
+    tr: Trigger<Damage>,
+    targets: Query<(
+        Option<&Health>,
+        Has<MaxHealth>,
+        Option<&Shield>,
+        Option<&Armor>,
+        Option<&Resistances>,
+        Option<&TemporaryHealth>,
+    )>,
     mut commands: Commands,
 ) {
     let entity = tr.target();
-    let (health, max_health) = health
-        .get(entity)
-        .expect("called adjust health on an entity witout MaxHealth");
+    let Ok((health, has_max, shield, armor, resistances, temp)) = targets.get(entity) else {
+        return reject(&mut commands, entity, HealthError::MissingEntity);
+    };
+    if !has_max {
+        return reject(&mut commands, entity, HealthError::MissingMaxHealth);
+    }
 
-    let new_health = max(
-        0,
-        min(max_health.0 as i32, health.map_or(0, |x| x.0 as i32) + tr.0),
-    ) as u32;
+    let mut amount = tr.amount;
 
-    if new_health == 0 {
-        commands.entity(entity).remove::<Health>();
-    } else {
-        commands.entity(entity).insert(Health(new_health));
+    let mut shield_left = shield.map_or(0, |s| s.0);
+    amount = absorb(amount, &mut shield_left);
+
+    if let DamageKind::Suit(suit) = tr.kind {
+        amount = amount.saturating_sub(armor.map_or(0, |a| a.0));
+        amount = amount.saturating_sub(resistances.map_or(0, |r| r.get(suit)));
+    }
+
+    let mut temp_left = temp.map_or(0, |t| t.0);
+    amount = absorb(amount, &mut temp_left);
+
+    let mut ec = commands.entity(entity);
+    if shield.is_some_and(|s| s.0 != shield_left) {
+        ec.insert(Shield(shield_left));
+    }
+    if temp.is_some_and(|t| t.0 != temp_left) {
+        ec.insert(TemporaryHealth(temp_left));
+    }
+
+    let prev = health.map_or(0, |h| h.0);
+    let new = prev.saturating_sub(amount);
+    if new != prev {
+        set_health(&mut ec, prev, new);
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn handle_heal(
+    tr: Trigger<Heal>,
+    targets: Query<(
+        Option<&Health>,
+        Option<&MaxHealth>,
+        Option<&Overheal>,
+        Option<&TemporaryHealth>,
+    )>,
+    mut commands: Commands,
+) {
+    let entity = tr.target();
+    let Ok((health, max_health, overheal, temp)) = targets.get(entity) else {
+        return reject(&mut commands, entity, HealthError::MissingEntity);
+    };
+    let Some(max_health) = max_health else {
+        return reject(&mut commands, entity, HealthError::MissingMaxHealth);
+    };
+
+    let prev = health.map_or(0, |x| x.0);
+    let health = prev + tr.amount;
+    let new = min(max_health.0, health);
+
+    let mut ec = commands.entity(entity);
+
+    if let Some(overheal) = overheal {
+        let excess = health - new;
+        let temp = min(overheal.0, temp.map_or(0, |t| t.0) + excess);
+        ec.insert(TemporaryHealth(temp));
+    }
+
+    if new > prev {
+        set_health(&mut ec, prev, new);
     }
 }
 
@@ -62,3 +378,106 @@ impl IntoStatusBar for Health {
     type GetValue = Health;
     type GetMaxValue = MaxHealth;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn world() -> World {
+        let mut world = World::new();
+        world.add_observer(handle_damage);
+        world.add_observer(handle_heal);
+        world
+    }
+
+    fn hit(world: &mut World, entity: Entity, amount: u32, kind: DamageKind) {
+        let damage = Damage {
+            amount,
+            kind,
+            source: None,
+        };
+        world.trigger_targets(damage, entity);
+        world.flush();
+    }
+
+    fn health(world: &World, entity: Entity) -> Option<u32> {
+        world.get::<Health>(entity).map(|h| h.0)
+    }
+
+    const SPADES: DamageKind = DamageKind::Suit(CardSuit::Spades);
+
+    #[test]
+    fn shield_absorbs_fully() {
+        let mut world = world();
+        let e = world.spawn((MaxHealth(20), Health(20), Shield(5))).id();
+
+        hit(&mut world, e, 3, DamageKind::True);
+        assert_eq!(health(&world, e), Some(20));
+        assert_eq!(world.get::<Shield>(e).map(|s| s.0), Some(2));
+    }
+
+    #[test]
+    fn shield_absorbs_partly() {
+        let mut world = world();
+        let e = world.spawn((MaxHealth(20), Health(20), Shield(5))).id();
+
+        hit(&mut world, e, 8, DamageKind::True);
+        assert_eq!(health(&world, e), Some(17));
+        assert_eq!(world.get::<Shield>(e).map(|s| s.0), Some(0));
+    }
+
+    #[test]
+    fn armor_never_heals() {
+        let mut world = world();
+        let e = world.spawn((MaxHealth(20), Health(20), Armor(10))).id();
+
+        hit(&mut world, e, 4, SPADES);
+        assert_eq!(health(&world, e), Some(20));
+    }
+
+    #[test]
+    fn true_damage_bypasses_armor_and_resistances() {
+        let mut world = world();
+        let resistances = Resistances::default().with(CardSuit::Spades, 3);
+        let e = world
+            .spawn((MaxHealth(20), Health(20), Armor(2), resistances))
+            .id();
+
+        hit(&mut world, e, 6, SPADES);
+        assert_eq!(health(&world, e), Some(19));
+
+        hit(&mut world, e, 6, DamageKind::True);
+        assert_eq!(health(&world, e), Some(13));
+    }
+
+    #[test]
+    fn overheal_becomes_temporary_health() {
+        let mut world = world();
+        let e = world.spawn((MaxHealth(20), Health(15), Overheal(4))).id();
+
+        world.trigger_targets(
+            Heal {
+                amount: 12,
+                source: None,
+            },
+            e,
+        );
+        world.flush();
+        assert_eq!(health(&world, e), Some(20));
+        assert_eq!(world.get::<TemporaryHealth>(e).map(|t| t.0), Some(4));
+
+        hit(&mut world, e, 6, DamageKind::True);
+        assert_eq!(health(&world, e), Some(18));
+        assert_eq!(world.get::<TemporaryHealth>(e).map(|t| t.0), Some(0));
+    }
+
+    #[test]
+    fn health_is_removed_at_zero() {
+        let mut world = world();
+        let e = world.spawn((MaxHealth(20), Health(5))).id();
+
+        hit(&mut world, e, 9, DamageKind::True);
+        assert!(world.entity(e).contains::<MaxHealth>());
+        assert_eq!(health(&world, e), None);
+    }
+}
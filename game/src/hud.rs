@@ -0,0 +1,137 @@
+use bevy::{color::palettes::css::GOLD, prelude::*};
+
+use crate::{
+    card::Card,
+    card_filter::CardFilter,
+    card_slot::{PlacementOfCard, RecievedCard},
+    discard::{Discard, DiscardedCards},
+    dungeon::{Deck, DrawCards, Room, RunStarted, Score, ScoreChanged, Seed, WeaponSlot},
+    status_bar::StatusBarStyle,
+    status_bar_source::StatusBarSource,
+    status_effect::GameTick,
+};
+
+#[derive(Component, Clone, Copy)]
+enum HudField {
+    DrawPile,
+    DiscardPile,
+    Room,
+    Score,
+    Seed,
+    Weapon,
+}
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_hud)
+            .add_observer(refresh_hud::<RunStarted>)
+            .add_observer(refresh_hud::<GameTick>)
+            .add_observer(refresh_hud::<DrawCards>)
+            .add_observer(refresh_hud::<RecievedCard>)
+            .add_observer(refresh_hud::<Discard>)
+            .add_observer(refresh_hud::<ScoreChanged>);
+    }
+}
+
+/// the fields are updated once the commands of the event are applied, as other observers of the
+/// same event may still draw, place or discard cards
+fn refresh_hud<E: Event>(_: Trigger<E>, mut commands: Commands) {
+    commands.run_system_cached(update_hud);
+}
+
+fn spawn_hud(mut commands: Commands) {
+    let root = commands
+        .spawn((
+            Name::new("Hud"),
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(12.0),
+                top: Val::Px(12.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                ..default()
+            },
+            GlobalZIndex(10),
+        ))
+        .id();
+
+    for field in [
+        HudField::DrawPile,
+        HudField::DiscardPile,
+        HudField::Room,
+        HudField::Score,
+        HudField::Seed,
+        HudField::Weapon,
+    ] {
+        commands.spawn((
+            ChildOf(root),
+            field,
+            Text::default(),
+            TextFont::from_font_size(18.0),
+        ));
+
+        if matches!(field, HudField::DrawPile) {
+            commands.spawn((
+                ChildOf(root),
+                Node {
+                    width: Val::Px(160.0),
+                    height: Val::Px(8.0),
+                    ..default()
+                },
+                children![(
+                    Node::default(),
+                    BackgroundColor(GOLD.into()),
+                    StatusBarStyle::default(),
+                    StatusBarSource::resource::<Deck>(|deck| deck.len() as f32)
+                        .with_max_resource::<Deck>(|deck| deck.size() as f32),
+                )],
+            ));
+        }
+    }
+    commands.run_system_cached(update_hud);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn update_hud(
+    mut fields: Query<(&HudField, &mut Text)>,
+    deck: Res<Deck>,
+    discarded: Query<&DiscardedCards>,
+    room: Res<Room>,
+    score: Res<Score>,
+    seed: Res<Seed>,
+    weapon_slots: Query<&PlacementOfCard, With<WeaponSlot>>,
+    weapons: Query<(&Card, Option<&CardFilter>)>,
+) {
+    let weapon = weapon_slots
+        .iter()
+        .find_map(|placed| weapons.get(placed.get()).ok());
+
+    for (field, mut text) in &mut fields {
+        let value = match field {
+            HudField::DrawPile => format!("Deck: {}", deck.len()),
+            HudField::DiscardPile => {
+                format!(
+                    "Discard: {}",
+                    discarded.iter().map(|d| d.len()).sum::<usize>()
+                )
+            }
+            HudField::Room => format!("Room: {}", room.0),
+            HudField::Score => format!("Score: {}", score.0),
+            HudField::Seed => format!("Seed: {}", seed.0),
+            HudField::Weapon => match weapon {
+                Some((card, filter)) => match filter.and_then(|f| f.max_rank()) {
+                    Some(cap) => format!("Weapon: {} (slays up to {})", card, cap),
+                    None => format!("Weapon: {}", card),
+                },
+                None => "Weapon: none".to_string(),
+            },
+        };
+
+        // only touched on change so the text isn't re-laid out for unrelated changes
+        if text.0 != value {
+            text.0 = value;
+        }
+    }
+}
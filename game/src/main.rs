@@ -5,29 +5,67 @@ mod enforce_exists;
 
 mod assets;
 mod card;
+mod card_definition;
 mod card_drag_drop;
 mod card_filter;
+mod card_focus;
+mod card_lock;
+mod card_quick_play;
+mod card_select;
 mod card_slot;
+mod deck_inspector;
 mod despawn;
+mod discard;
+mod dungeon;
+mod effect;
 mod health;
+mod hud;
+mod menu;
+mod monster;
+mod player;
+mod rules;
+mod settings;
 mod sprite_repr;
 mod status_bar;
+mod status_bar_source;
+mod status_effect;
+mod validation;
 
 use assets::AssetHandles;
 use bevy::{
-    color::palettes::css::{GRAY, RED},
+    color::palettes::css::RED,
     prelude::*,
     remote::{RemotePlugin, http::RemoteHttpPlugin},
     sprite::Anchor,
 };
-use card::{Card, CardSuit};
-use card_drag_drop::CardDragDropPlugin;
+use card::Card;
+use card_definition::{CardCategory, CardDefinitionPlugin};
+use card_drag_drop::{CardDragDropPlugin, SnapRadius};
 use card_filter::CardFilter;
-use card_slot::{CardSlotPlugin, CardSlotSprite, PlacedOnSlot, PlacementOfCard, RecievedCard};
-use despawn::{DespawnDelayed, DespawnPlugin};
-use health::{AdjustHealth, Health, HealthPlugin, MaxHealth};
+use card_focus::CardFocusPlugin;
+use card_lock::{CardLockPlugin, LocksPlacedCards};
+use card_quick_play::{CardQuickPlayPlugin, QuickPlayTarget};
+use card_select::CardSelectPlugin;
+use card_slot::{CardSlotPlugin, CardSlotSprite, PlacedOnSlot, PreviousSlot, RecievedCard};
+use deck_inspector::DeckInspectorPlugin;
+use despawn::DespawnPlugin;
+use discard::{DiscardPile, DiscardPlugin};
+use dungeon::{
+    Deck, DungeonPlugin, RoomSlot, RunScoped, RunStarted, Score, ScoreChanged, WeaponSlot,
+};
+use effect::{CardScripts, EffectPlugin, PlayedOn, RunEffects, report_missing_script};
+use health::{Health, HealthPlugin, MaxHealth};
+use hud::HudPlugin;
+use menu::MenuPlugin;
+use monster::MonsterPlugin;
+use player::{Player, PlayerBody, PlayerWeapon};
+use rules::{CardRole, RuleSet, RulesPlugin};
+use settings::SettingsPlugin;
 use sprite_repr::SpriteReprPlugin;
-use status_bar::{StatusBarOf, StatusBarPlugin, StatusBarType};
+use status_bar::{StatusBarLabel, StatusBarOf, StatusBarPlugin, StatusBarStyle, StatusBarType};
+use status_bar_source::StatusBarSourcePlugin;
+use status_effect::{EffectIcons, StatusEffectPlugin, StatusEffects};
+use validation::ValidationPlugin;
 fn main() {
     App::new()
         .add_plugins((
@@ -38,17 +76,48 @@ fn main() {
             DefaultPlugins,
             SpriteReprPlugin,
             CardDragDropPlugin,
+            CardLockPlugin,
+            CardFocusPlugin,
+            CardQuickPlayPlugin,
+            CardSelectPlugin,
             CardSlotPlugin,
             HealthPlugin,
             StatusBarPlugin::<Health>::default(),
             DespawnPlugin::<PostUpdate>::default(),
+            ValidationPlugin,
+            StatusEffectPlugin,
+            MonsterPlugin,
+        ))
+        .add_plugins((
+            StatusBarSourcePlugin,
+            DungeonPlugin,
+            HudPlugin,
+            DiscardPlugin,
+            DeckInspectorPlugin,
+            MenuPlugin,
+            SettingsPlugin,
+            RulesPlugin,
+            CardDefinitionPlugin,
+            EffectPlugin,
         ))
         .insert_resource(SpritePickingSettings {
             picking_mode: SpritePickingMode::BoundingBox,
             ..Default::default()
         })
         .add_systems(PreStartup, load_assets)
-        .add_systems(Startup, setup_scene)
+        .add_systems(Startup, spawn_camera)
+        .add_observer(setup_scene)
+        .add_systems(
+            Update,
+            update_score.run_if(
+                resource_changed::<Deck>
+                    .or(resource_changed::<RuleSet>)
+                    .or(any_match_filter::<(With<Player>, Changed<Health>)>)
+                    .or(any_match_filter::<Changed<PlacedOnSlot>>)
+                    .or(any_component_removed::<PlacedOnSlot>)
+                    .or(any_component_removed::<Health>),
+            ),
+        )
         .run();
 }
 
@@ -56,69 +125,50 @@ fn load_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands.insert_resource(AssetHandles::load(&asset_server));
 }
 
-fn setup_scene(mut commands: Commands) {
+fn spawn_camera(mut commands: Commands) {
     commands.spawn(Camera2d);
+}
 
-    let player = commands.spawn((Player, MaxHealth(20), Health(20))).id();
+fn setup_scene(_: Trigger<RunStarted>, rules: Res<RuleSet>, mut commands: Commands) {
+    let health = rules.starting_health();
+    let player = commands
+        .spawn((
+            Player,
+            RunScoped,
+            MaxHealth(health),
+            Health(health),
+            StatusEffects::default(),
+        ))
+        .id();
 
-    // Cards
-    commands.spawn((
-        CardSlotSprite::Empty,
-        Pickable::default(),
-        Transform::from_xyz(-300.0, 200.0, 0.0),
-        related![
-            PlacementOfCard[(
-                Name::new("Card H11"),
-                Card::new(CardSuit::Hearts, 11),
-                Pickable::default(),
-            )]
-        ],
-    ));
-    commands.spawn((
-        CardSlotSprite::Empty,
-        Pickable::default(),
-        Transform::from_xyz(-100.0, 200.0, 0.0),
-        related![
-            PlacementOfCard[(
-                Name::new("Card D10"),
-                Card::new(CardSuit::Diamonds, 10),
-                Pickable::default(),
-            )]
-        ],
-    ));
-    commands.spawn((
-        CardSlotSprite::Empty,
-        Pickable::default(),
-        Transform::from_xyz(100.0, 200.0, 0.0),
-        related![
-            PlacementOfCard[(
-                Name::new("Card D13"),
-                Card::new(CardSuit::Diamonds, 13),
-                Pickable::default(),
-            )]
-        ],
-    ));
-    commands.spawn((
-        CardSlotSprite::Empty,
-        Pickable::default(),
-        Transform::from_xyz(300.0, 200.0, 0.0),
-        related![
-            PlacementOfCard[(
-                Name::new("Card C10"),
-                Card::new(CardSuit::Clubs, 10),
-                Pickable::default(),
-            )]
-        ],
-    ));
+    // Room
+    let room_size = rules.room_size();
+    for idx in 0..room_size {
+        let x = (idx as f32 - (room_size - 1) as f32 / 2.0) * 200.0;
+        commands.spawn((
+            RoomSlot,
+            RunScoped,
+            CardSlotSprite::Empty,
+            Pickable::default(),
+            Transform::from_xyz(x, 200.0, 0.0),
+        ));
+    }
 
     // Card Slots
     let body = commands
-        .spawn((Name::new("Body"), CardSlotSprite::Body, Pickable::default()))
+        .spawn((
+            Name::new("Body"),
+            CardSlotSprite::Body,
+            SnapRadius(120.0),
+            QuickPlayTarget(0),
+            Pickable::default(),
+        ))
         .observe(handle_card_on_body)
         .id();
 
     commands
         .spawn((
+            RunScoped,
             Transform::from_xyz(-100.0, -150.0, 0.0),
             InheritedVisibility::default(),
             children![(
@@ -128,6 +178,8 @@ fn setup_scene(mut commands: Commands) {
                     (
                         StatusBarOf(player),
                         StatusBarType::<Health>::default(),
+                        StatusBarLabel::default(),
+                        StatusBarStyle::health(),
                         Sprite {
                             color: RED.into(),
                             custom_size: Some(Vec2::new(145.0, 35.0)),
@@ -135,15 +187,7 @@ fn setup_scene(mut commands: Commands) {
                             ..default()
                         }
                     ),
-                    (
-                        Transform::from_xyz(0.0, 0.0, -1.0),
-                        Sprite {
-                            color: GRAY.into(),
-                            custom_size: Some(Vec2::new(145.0, 35.0)),
-                            anchor: Anchor::CenterLeft,
-                            ..default()
-                        }
-                    )
+                    (EffectIcons(player), Transform::from_xyz(15.0, -40.0, 0.0))
                 ]
             )],
         ))
@@ -152,73 +196,104 @@ fn setup_scene(mut commands: Commands) {
     let weapon = commands
         .spawn((
             Name::new("Weapon"),
+            RunScoped,
+            WeaponSlot,
             CardSlotSprite::Weapon,
+            LocksPlacedCards,
+            SnapRadius(120.0),
+            QuickPlayTarget(1),
             Pickable::default(),
             Transform::from_xyz(100.0, -150.0, 0.0),
-            CardFilter::empty().with_suit([CardSuit::Diamonds]),
+            CardFilter::empty()
+                .with_suit(rules.suits(CardRole::Weapon))
+                .with_categories([CardCategory::Weapon]),
         ))
+        .observe(handle_card_on_weapon_slot)
         .id();
 
+    commands.spawn((
+        Name::new("Discard Pile"),
+        RunScoped,
+        DiscardPile,
+        CardSlotSprite::Empty,
+        Pickable::default(),
+        Transform::from_xyz(300.0, -150.0, 0.0),
+    ));
+
     commands
         .entity(player)
         .insert((PlayerBody(body), PlayerWeapon(weapon)));
 }
 
-#[derive(Component)]
-#[relationship(relationship_target=PlayerWeaponOf)]
-struct PlayerWeapon(Entity);
-
-#[derive(Component)]
-#[relationship_target(relationship=PlayerWeapon)]
-struct PlayerWeaponOf(Entity);
-
-#[derive(Component)]
-#[relationship(relationship_target=PlayerBodyOf)]
-struct PlayerBody(Entity);
-
-#[derive(Component)]
-#[relationship_target(relationship=PlayerBody)]
-struct PlayerBodyOf(Entity);
-
-#[derive(Component)]
-#[require(Name = Name::new("Player"))]
-struct Player;
-
+/// runs the script of the card's role, then the effects of its definition if it has one
 fn handle_card_on_body(
     tr: Trigger<RecievedCard>,
-    card_slots: Query<&PlacementOfCard>,
     cards: Query<&Card, With<PlacedOnSlot>>,
-    player: Query<(Entity, &PlayerWeapon), With<Player>>,
+    previous_slots: Query<&PreviousSlot>,
+    card_scripts: CardScripts,
     mut commands: Commands,
 ) {
     let card_e = tr.0;
     let card = cards.get(card_e).unwrap();
-    let (player_e, weapon) = player.single().unwrap();
-
-    match card.suit() {
-        CardSuit::Hearts => {
-            commands
-                .entity(player_e)
-                .trigger(AdjustHealth(card.rank() as i32));
 
-            commands.entity(card_e).trigger(DespawnDelayed);
+    // it goes back so the run can go on
+    let Some(effects) = card_scripts.effects(card, PlayedOn::Body) else {
+        report_missing_script(&mut commands, card_e, card);
+        if let Ok(prev) = previous_slots.get(card_e) {
+            commands.entity(card_e).insert(PlacedOnSlot(prev.0));
         }
-        CardSuit::Diamonds => {
-            if let Ok(weapon_slot) = card_slots.get(weapon.0) {
-                commands
-                    .entity(weapon_slot.get())
-                    .remove::<PlacedOnSlot>()
-                    .trigger(DespawnDelayed);
-            }
-
-            commands.entity(card_e).insert(PlacedOnSlot(weapon.0));
-        }
-        CardSuit::Clubs | CardSuit::Spades => {
-            commands
-                .entity(player_e)
-                .trigger(AdjustHealth(-(card.rank() as i32)));
+        return;
+    };
+    commands.entity(card_e).trigger(RunEffects(effects));
+}
+
+/// runs the equip script, the equipped weapon becomes a slot accepting monsters it can still fight
+fn handle_card_on_weapon_slot(
+    tr: Trigger<RecievedCard>,
+    cards: Query<&Card>,
+    card_scripts: CardScripts,
+    mut commands: Commands,
+) {
+    let card_e = tr.0;
+    let card = cards.get(card_e).unwrap();
 
-            commands.entity(card_e).trigger(DespawnDelayed);
+    match card_scripts.effects(card, PlayedOn::WeaponSlot) {
+        Some(effects) => {
+            commands.entity(card_e).trigger(RunEffects(effects));
         }
+        // stays equipped without taking monsters, sending it back to the body would equip it again
+        None => report_missing_script(&mut commands, card_e, card),
+    }
+}
+
+/// the player's health minus every monster still in the dungeon, only run when either changes
+fn update_score(
+    player: Query<Option<&Health>, With<Player>>,
+    room_cards: Query<(&Card, &PlacedOnSlot)>,
+    room_slots: Query<(), With<RoomSlot>>,
+    deck: Res<Deck>,
+    rules: Res<RuleSet>,
+    mut score: ResMut<Score>,
+    mut commands: Commands,
+) {
+    let Ok(health) = player.single() else {
+        return;
+    };
+    let is_monster = |card: &Card| rules.role(card) == CardRole::Monster;
+
+    let in_room = room_cards
+        .iter()
+        .filter(|(card, slot)| room_slots.contains(slot.0) && is_monster(card))
+        .map(|(card, _)| rules.value(card));
+    let in_deck = deck
+        .cards
+        .iter()
+        .filter(|c| is_monster(c))
+        .map(|c| rules.value(c));
+    let monsters: u32 = in_room.chain(in_deck).sum();
+
+    let new = health.map_or(0, |h| h.0) as i32 - monsters as i32;
+    if score.set_if_neq(Score(new)) {
+        commands.trigger(ScoreChanged);
     }
 }
@@ -0,0 +1,230 @@
+use bevy::{
+    color::palettes::css::{DARK_SLATE_GRAY, SLATE_GRAY},
+    prelude::*,
+};
+
+use crate::{
+    dungeon::{NewRun, RunScoped},
+    rules::{CliRules, RuleSet},
+    settings::{Settings, SettingsScreen},
+};
+
+#[derive(States, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AppState {
+    #[default]
+    MainMenu,
+    InGame,
+}
+
+/// game input and virtual time only run while [`GamePhase::Running`]
+#[derive(SubStates, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[source(AppState = AppState::InGame)]
+pub enum GamePhase {
+    #[default]
+    Running,
+    Paused,
+}
+
+/// whether game input is handled, a run condition also called by the input observers which
+/// can't take one
+pub fn game_running(phase: Option<Res<State<GamePhase>>>) -> bool {
+    phase.is_some_and(|phase| *phase.get() == GamePhase::Running)
+}
+
+/// highlighted while hovered
+#[derive(Component, Default)]
+#[require(Button)]
+pub struct MenuButton;
+
+#[derive(Component, Clone, Copy)]
+enum MenuAction {
+    NewGame,
+    /// back to the run left for the main menu, runs aren't saved so there is none after a restart
+    Continue,
+    Settings,
+    Quit,
+    Resume,
+    MainMenu,
+}
+
+pub struct MenuPlugin;
+
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_state::<AppState>()
+            .add_sub_state::<GamePhase>()
+            .enable_state_scoped_entities::<AppState>()
+            .enable_state_scoped_entities::<GamePhase>()
+            .add_systems(OnEnter(AppState::MainMenu), spawn_main_menu)
+            .add_systems(OnEnter(GamePhase::Paused), spawn_pause_menu)
+            .add_systems(OnEnter(GamePhase::Running), unpause_time)
+            .add_systems(OnExit(GamePhase::Running), pause_time)
+            .add_systems(
+                Update,
+                (
+                    toggle_pause.run_if(in_state(AppState::InGame)),
+                    handle_menu_buttons.run_if(in_state(SettingsScreen::Closed)),
+                    highlight_buttons,
+                ),
+            );
+    }
+}
+
+/// a wide button with a text label, used by every menu screen
+pub fn menu_button(label: &str) -> impl Bundle {
+    (
+        MenuButton,
+        Node {
+            width: Val::Px(240.0),
+            height: Val::Px(48.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(DARK_SLATE_GRAY.into()),
+        children![(Text::new(label), TextFont::from_font_size(22.0))],
+    )
+}
+
+fn menu_screen(background: Color) -> impl Bundle {
+    (
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            row_gap: Val::Px(12.0),
+            ..default()
+        },
+        BackgroundColor(background),
+        GlobalZIndex(30),
+    )
+}
+
+fn spawn_main_menu(runs: Query<(), With<RunScoped>>, mut commands: Commands) {
+    let menu = commands
+        .spawn((
+            Name::new("Main Menu"),
+            StateScoped(AppState::MainMenu),
+            menu_screen(Color::srgb(0.05, 0.05, 0.08)),
+        ))
+        .id();
+
+    commands.spawn((
+        ChildOf(menu),
+        Text::new("Scoundrel"),
+        TextFont::from_font_size(48.0),
+    ));
+
+    let continue_run = !runs.is_empty();
+    for (label, action) in [
+        ("New Game", MenuAction::NewGame),
+        ("Back to Run", MenuAction::Continue),
+        ("Settings", MenuAction::Settings),
+        ("Quit", MenuAction::Quit),
+    ] {
+        if matches!(action, MenuAction::Continue) && !continue_run {
+            continue;
+        }
+        commands.spawn((ChildOf(menu), menu_button(label), action));
+    }
+}
+
+fn spawn_pause_menu(mut commands: Commands) {
+    let menu = commands
+        .spawn((
+            Name::new("Pause Menu"),
+            StateScoped(GamePhase::Paused),
+            menu_screen(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+        ))
+        .id();
+
+    commands.spawn((
+        ChildOf(menu),
+        Text::new("Paused"),
+        TextFont::from_font_size(40.0),
+    ));
+
+    for (label, action) in [
+        ("Resume", MenuAction::Resume),
+        ("Settings", MenuAction::Settings),
+        ("Main Menu", MenuAction::MainMenu),
+    ] {
+        commands.spawn((ChildOf(menu), menu_button(label), action));
+    }
+}
+
+fn pause_time(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+fn unpause_time(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
+
+fn toggle_pause(
+    keys: Res<ButtonInput<KeyCode>>,
+    phase: Res<State<GamePhase>>,
+    settings_screen: Res<State<SettingsScreen>>,
+    mut next_phase: ResMut<NextState<GamePhase>>,
+) {
+    // escape closes the settings screen first
+    if !keys.just_pressed(KeyCode::Escape) || *settings_screen.get() == SettingsScreen::Open {
+        return;
+    }
+
+    next_phase.set(match phase.get() {
+        GamePhase::Running => GamePhase::Paused,
+        GamePhase::Paused => GamePhase::Running,
+    });
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_menu_buttons(
+    buttons: Query<(&Interaction, &MenuAction), Changed<Interaction>>,
+    settings: Res<Settings>,
+    cli_rules: Res<CliRules>,
+    mut app_state: ResMut<NextState<AppState>>,
+    mut phase: ResMut<NextState<GamePhase>>,
+    mut settings_screen: ResMut<NextState<SettingsScreen>>,
+    mut exit: EventWriter<AppExit>,
+    mut commands: Commands,
+) {
+    for (interaction, action) in &buttons {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        match action {
+            MenuAction::NewGame => {
+                commands.insert_resource(RuleSet::from(cli_rules.preset(&settings)));
+                commands.trigger(NewRun);
+                app_state.set(AppState::InGame);
+            }
+            MenuAction::Continue => app_state.set(AppState::InGame),
+            MenuAction::Settings => settings_screen.set(SettingsScreen::Open),
+            MenuAction::Quit => {
+                exit.write(AppExit::Success);
+            }
+            MenuAction::Resume => phase.set(GamePhase::Running),
+            MenuAction::MainMenu => app_state.set(AppState::MainMenu),
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn highlight_buttons(
+    mut buttons: Query<
+        (&Interaction, &mut BackgroundColor),
+        (With<MenuButton>, Changed<Interaction>),
+    >,
+) {
+    for (interaction, mut bg) in &mut buttons {
+        bg.0 = match interaction {
+            Interaction::None => DARK_SLATE_GRAY.into(),
+            Interaction::Hovered | Interaction::Pressed => SLATE_GRAY.into(),
+        };
+    }
+}
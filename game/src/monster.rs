@@ -0,0 +1,100 @@
+use bevy::{color::palettes::css::DARK_RED, prelude::*, sprite::Anchor};
+
+use crate::{
+    card::Card,
+    discard::InDiscardPile,
+    health::{Health, MaxHealth},
+    rules::{CardRole, RuleSet},
+    sprite_repr::CARD_SIZE,
+    status_bar::{Segments, StatusBarOf, StatusBarStyle, StatusBarType},
+};
+
+const BAR_HEIGHT: f32 = 12.0;
+
+/// how monsters are resolved
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CombatMode {
+    /// monsters resolve instantly against the player
+    #[default]
+    Classic,
+    /// monsters have health equal to their rank and counterattack when they survive a strike
+    Deep,
+}
+
+#[derive(Component)]
+pub struct Monster;
+
+#[derive(Component)]
+struct MonsterHealthBar(Entity);
+
+pub struct MonsterPlugin;
+
+impl Plugin for MonsterPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CombatMode>()
+            .add_observer(handle_card_added)
+            .add_observer(handle_monster_discarded);
+    }
+}
+
+fn handle_card_added(
+    tr: Trigger<OnInsert, Card>,
+    cards: Query<&Card>,
+    mode: Res<CombatMode>,
+    rules: Res<RuleSet>,
+    mut commands: Commands,
+) {
+    if *mode != CombatMode::Deep {
+        return;
+    }
+
+    let entity = tr.target();
+    let card = cards.get(entity).unwrap();
+    if rules.role(card) != CardRole::Monster {
+        return;
+    }
+
+    let value = rules.value(card);
+    commands
+        .entity(entity)
+        .insert((Monster, MaxHealth(value), Health(value)));
+
+    let size = Vec2::new(CARD_SIZE.x, BAR_HEIGHT);
+    let bar = commands
+        .spawn((
+            Name::new("Monster Health Bar"),
+            ChildOf(entity),
+            Transform::from_xyz(
+                -CARD_SIZE.x / 2.0,
+                (CARD_SIZE.y + BAR_HEIGHT) / 2.0 + 4.0,
+                2.0,
+            ),
+            Visibility::default(),
+            children![(
+                StatusBarOf(entity),
+                StatusBarType::<Health>::default(),
+                StatusBarStyle::default().with_segments(Segments::PerPoint),
+                Sprite {
+                    color: DARK_RED.into(),
+                    custom_size: Some(size),
+                    anchor: Anchor::CenterLeft,
+                    ..default()
+                }
+            )],
+        ))
+        .id();
+    commands.entity(entity).insert(MonsterHealthBar(bar));
+}
+
+/// the health bar would stick out of the pile
+fn handle_monster_discarded(
+    tr: Trigger<OnInsert, InDiscardPile>,
+    bars: Query<&MonsterHealthBar>,
+    mut commands: Commands,
+) {
+    let Ok(bar) = bars.get(tr.target()) else {
+        return;
+    };
+    commands.entity(bar.0).despawn();
+    commands.entity(tr.target()).remove::<MonsterHealthBar>();
+}
@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+
+#[derive(Component)]
+#[relationship(relationship_target=PlayerWeaponOf)]
+pub struct PlayerWeapon(pub Entity);
+
+#[derive(Component)]
+#[relationship_target(relationship=PlayerWeapon)]
+pub struct PlayerWeaponOf(Entity);
+
+#[derive(Component)]
+#[relationship(relationship_target=PlayerBodyOf)]
+pub struct PlayerBody(pub Entity);
+
+#[derive(Component)]
+#[relationship_target(relationship=PlayerBody)]
+pub struct PlayerBodyOf(Entity);
+
+#[derive(Component)]
+#[require(Name = Name::new("Player"))]
+pub struct Player;
+
+/// left by a [`crate::card::JokerEffect::DoublePotion`] joker, used up by the next potion
+/// that heals
+#[derive(Component)]
+pub struct DoubleNextPotion;
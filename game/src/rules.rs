@@ -0,0 +1,296 @@
+use std::{fmt, ops::Deref, str::FromStr};
+
+use bevy::prelude::*;
+use enum_iterator::{Sequence, all};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    card::{Card, CardSuit, JokerEffect},
+    card_definition::CardCategory,
+    dungeon::RoomState,
+    monster::CombatMode,
+    settings::Settings,
+};
+
+/// what a card does when played
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CardRole {
+    /// heals its value when played on the body
+    Potion,
+    /// equipped when played on the body, blocks its value
+    Weapon,
+    /// deals its value, minus the weapon it's fought with
+    Monster,
+    /// resolved by its [`JokerEffect`] when played on the body
+    Joker,
+    /// a custom card resolved by its effects only
+    Item,
+}
+
+/// the rules of a run, every method has the classic Scoundrel behavior as default
+pub trait Rules: Send + Sync + 'static {
+    fn starting_health(&self) -> u32 {
+        20
+    }
+
+    fn room_size(&self) -> usize {
+        4
+    }
+
+    /// a new room is dealt once no more than this many cards are left in the current one
+    fn room_carry_over(&self) -> usize {
+        1
+    }
+
+    /// potions that heal in a single room, the rest are discarded without effect
+    fn potions_per_room(&self) -> Option<u32> {
+        Some(1)
+    }
+
+    /// a room can be skipped before playing any of its cards, but never twice in a row
+    fn can_avoid(&self, room: &RoomState) -> bool {
+        room.played == 0 && !room.avoided
+    }
+
+    /// the black cards and the red cards without faces and aces, plus the [`Rules::jokers`]
+    fn deck(&self) -> Vec<Card> {
+        all::<CardSuit>()
+            .flat_map(|suit| (1..=13).map(move |rank| (suit, rank)))
+            .filter(|(suit, rank)| match suit {
+                CardSuit::Hearts | CardSuit::Diamonds => (2..=10).contains(rank),
+                CardSuit::Clubs | CardSuit::Spades => true,
+            })
+            .map(|(suit, rank)| Card::new(suit, rank))
+            .chain(self.jokers().into_iter().map(Card::joker))
+            .collect()
+    }
+
+    /// shuffles every loaded [`crate::card_definition::CardDefinition`] into the deck
+    fn custom_cards(&self) -> bool {
+        false
+    }
+
+    /// one joker is shuffled into the deck for each effect
+    fn jokers(&self) -> Vec<JokerEffect> {
+        Vec::new()
+    }
+
+    fn suit_role(&self, suit: CardSuit) -> CardRole {
+        match suit {
+            CardSuit::Hearts => CardRole::Potion,
+            CardSuit::Diamonds => CardRole::Weapon,
+            CardSuit::Clubs | CardSuit::Spades => CardRole::Monster,
+        }
+    }
+
+    fn role(&self, card: &Card) -> CardRole {
+        if let Some(definition) = card.definition() {
+            return match definition.category {
+                CardCategory::Monster => CardRole::Monster,
+                CardCategory::Weapon => CardRole::Weapon,
+                CardCategory::Potion => CardRole::Potion,
+                CardCategory::Item => CardRole::Item,
+            };
+        }
+        match (card.suit(), card.joker_effect()) {
+            (Some(suit), _) => self.suit_role(suit),
+            (None, Some(JokerEffect::AnyMonster)) => CardRole::Monster,
+            (None, _) => CardRole::Joker,
+        }
+    }
+
+    /// the rank a [`JokerEffect::AnyMonster`] joker fights as
+    fn joker_rank(&self) -> u32 {
+        10
+    }
+
+    /// aces are worth more than the kings
+    fn ace_value(&self) -> u32 {
+        14
+    }
+
+    /// how much a potion heals, a weapon blocks or a monster hits
+    fn value(&self, card: &Card) -> u32 {
+        match (card.definition(), card.rank()) {
+            (Some(definition), _) => definition.value,
+            (None, Some(1)) => self.ace_value(),
+            (None, Some(rank)) => rank,
+            (None, None) => self.joker_rank(),
+        }
+    }
+
+    fn combat_mode(&self) -> CombatMode {
+        CombatMode::Classic
+    }
+
+    /// see [`crate::dungeon::Hardcore`]
+    fn hardcore(&self) -> bool {
+        false
+    }
+
+    fn suits(&self, role: CardRole) -> Vec<CardSuit> {
+        all::<CardSuit>()
+            .filter(|s| self.suit_role(*s) == role)
+            .collect()
+    }
+}
+
+struct Classic;
+
+impl Rules for Classic {}
+
+struct DeepCombat;
+
+impl Rules for DeepCombat {
+    fn combat_mode(&self) -> CombatMode {
+        CombatMode::Deep
+    }
+}
+
+/// less health and rooms can't be avoided
+struct HardMode;
+
+impl Rules for HardMode {
+    fn starting_health(&self) -> u32 {
+        15
+    }
+
+    fn can_avoid(&self, _: &RoomState) -> bool {
+        false
+    }
+}
+
+struct Hardcore;
+
+impl Rules for Hardcore {
+    fn hardcore(&self) -> bool {
+        true
+    }
+}
+
+/// one joker of each effect
+struct Jokers;
+
+impl Rules for Jokers {
+    fn jokers(&self) -> Vec<JokerEffect> {
+        all::<JokerEffect>().collect()
+    }
+}
+
+/// the custom cards on top of the classic deck
+struct Roguelike;
+
+impl Rules for Roguelike {
+    fn custom_cards(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Serialize, Deserialize, Sequence)]
+pub enum RulesPreset {
+    #[default]
+    Classic,
+    DeepCombat,
+    Hard,
+    Hardcore,
+    Jokers,
+    Roguelike,
+}
+
+impl RulesPreset {
+    pub fn label(&self) -> &'static str {
+        match self {
+            RulesPreset::Classic => "Classic",
+            RulesPreset::DeepCombat => "Deep Combat",
+            RulesPreset::Hard => "Hard",
+            RulesPreset::Hardcore => "Hardcore",
+            RulesPreset::Jokers => "Jokers",
+            RulesPreset::Roguelike => "Roguelike",
+        }
+    }
+
+    /// the preset passed as `--rules <name>`
+    pub fn from_args() -> Option<Self> {
+        let mut args = std::env::args().skip_while(|arg| arg != "--rules").skip(1);
+        let name = args.next()?;
+        name.parse().inspect_err(|err| error!("{}", err)).ok()
+    }
+}
+
+/// the CLI names of the presets
+impl fmt::Display for RulesPreset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            RulesPreset::Classic => "classic",
+            RulesPreset::DeepCombat => "deep",
+            RulesPreset::Hard => "hard",
+            RulesPreset::Hardcore => "hardcore",
+            RulesPreset::Jokers => "jokers",
+            RulesPreset::Roguelike => "roguelike",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for RulesPreset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        all::<RulesPreset>()
+            .find(|preset| preset.to_string() == s)
+            .ok_or_else(|| {
+                let names: Vec<_> = all::<RulesPreset>().map(|p| p.to_string()).collect();
+                format!(
+                    "unknown rules '{}', expected one of {}",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+/// the preset passed as `--rules`, takes over the one from the settings for every new run
+/// without being saved to them
+#[derive(Resource, Default)]
+pub struct CliRules(pub Option<RulesPreset>);
+
+impl CliRules {
+    /// the preset of the next run
+    pub fn preset(&self, settings: &Settings) -> RulesPreset {
+        self.0.unwrap_or(settings.rules)
+    }
+}
+
+/// the rules of the current run, replaced when a new run starts
+#[derive(Resource)]
+pub struct RuleSet(Box<dyn Rules>);
+
+impl From<RulesPreset> for RuleSet {
+    fn from(preset: RulesPreset) -> Self {
+        RuleSet(match preset {
+            RulesPreset::Classic => Box::new(Classic),
+            RulesPreset::DeepCombat => Box::new(DeepCombat),
+            RulesPreset::Hard => Box::new(HardMode),
+            RulesPreset::Hardcore => Box::new(Hardcore),
+            RulesPreset::Jokers => Box::new(Jokers),
+            RulesPreset::Roguelike => Box::new(Roguelike),
+        })
+    }
+}
+
+impl Deref for RuleSet {
+    type Target = dyn Rules;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref()
+    }
+}
+
+pub struct RulesPlugin;
+
+impl Plugin for RulesPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(RuleSet::from(RulesPreset::default()))
+            .insert_resource(CliRules(RulesPreset::from_args()));
+    }
+}
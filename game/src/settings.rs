@@ -0,0 +1,307 @@
+use std::{fs, path::PathBuf};
+
+use bevy::{
+    audio::Volume,
+    color::palettes::css::DARK_SLATE_GRAY,
+    prelude::*,
+    window::{PrimaryWindow, WindowMode},
+};
+use enum_iterator::{next_cycle, previous_cycle};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    menu::{MenuButton, menu_button},
+    rules::RulesPreset,
+};
+
+const VOLUME_STEP: f32 = 0.1;
+const CARD_SCALE_STEP: f32 = 0.1;
+const ANIMATION_SPEED_STEP: f32 = 0.25;
+
+/// persisted to `settings.ron` in the user config directory, rules apply from the next run
+#[derive(Resource, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub volume: f32,
+    pub fullscreen: bool,
+    pub card_scale: f32,
+    pub animation_speed: f32,
+    pub rules: RulesPreset,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            fullscreen: false,
+            card_scale: 1.0,
+            animation_speed: 1.0,
+            rules: RulesPreset::Classic,
+        }
+    }
+}
+
+impl Settings {
+    fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("scoundrel").join("settings.ron"))
+    }
+
+    /// falls back to the defaults if there is no config file or it can't be read
+    pub fn load() -> Self {
+        let Some(path) = Self::path() else {
+            return default();
+        };
+        let Ok(text) = fs::read_to_string(&path) else {
+            return default();
+        };
+        ron::from_str(&text).unwrap_or_else(|err| {
+            warn!("ignoring invalid settings in {}: {}", path.display(), err);
+            default()
+        })
+    }
+
+    pub fn save(&self) {
+        let Some(path) = Self::path() else {
+            return;
+        };
+        let result = ron::ser::to_string_pretty(self, default())
+            .map_err(|err| err.to_string())
+            .and_then(|text| {
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+                }
+                fs::write(&path, text).map_err(|err| err.to_string())
+            });
+        if let Err(err) = result {
+            error!("failed to save settings to {}: {}", path.display(), err);
+        }
+    }
+}
+
+/// shown on top of the main or pause menu
+#[derive(States, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SettingsScreen {
+    #[default]
+    Closed,
+    Open,
+}
+
+#[derive(Component, Clone, Copy)]
+enum SettingField {
+    Volume,
+    Fullscreen,
+    CardScale,
+    AnimationSpeed,
+    Rules,
+}
+
+#[derive(Component, Clone, Copy)]
+enum SettingsButton {
+    Decrease(SettingField),
+    Increase(SettingField),
+    Back,
+}
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Settings::load())
+            .init_state::<SettingsScreen>()
+            .enable_state_scoped_entities::<SettingsScreen>()
+            .add_systems(OnEnter(SettingsScreen::Open), spawn_settings_screen)
+            .add_systems(OnExit(SettingsScreen::Open), save_settings)
+            .add_systems(
+                Update,
+                (
+                    (close_on_escape, handle_settings_buttons)
+                        .run_if(in_state(SettingsScreen::Open)),
+                    update_setting_values
+                        .run_if(resource_changed::<Settings>.or(state_changed::<SettingsScreen>)),
+                    apply_settings.run_if(resource_changed::<Settings>),
+                )
+                    .chain(),
+            );
+    }
+}
+
+fn save_settings(settings: Res<Settings>) {
+    settings.save();
+}
+
+fn spawn_settings_screen(mut commands: Commands) {
+    let screen = commands
+        .spawn((
+            Name::new("Settings Screen"),
+            StateScoped(SettingsScreen::Open),
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                row_gap: Val::Px(8.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.05, 0.05, 0.08)),
+            GlobalZIndex(40),
+        ))
+        .id();
+
+    for (field, label) in [
+        (SettingField::Volume, "Volume"),
+        (SettingField::Fullscreen, "Fullscreen"),
+        (SettingField::CardScale, "Card Scale"),
+        (SettingField::AnimationSpeed, "Animation Speed"),
+        (SettingField::Rules, "Rules"),
+    ] {
+        commands.spawn((
+            ChildOf(screen),
+            Node {
+                column_gap: Val::Px(12.0),
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            children![
+                (
+                    Text::new(label),
+                    Node {
+                        width: Val::Px(180.0),
+                        ..default()
+                    }
+                ),
+                small_button("<", SettingsButton::Decrease(field)),
+                (
+                    field,
+                    Text::default(),
+                    TextLayout::new_with_justify(JustifyText::Center),
+                    Node {
+                        width: Val::Px(140.0),
+                        ..default()
+                    }
+                ),
+                small_button(">", SettingsButton::Increase(field)),
+            ],
+        ));
+    }
+
+    commands.spawn((ChildOf(screen), menu_button("Back"), SettingsButton::Back));
+}
+
+fn small_button(label: &str, action: SettingsButton) -> impl Bundle {
+    (
+        MenuButton,
+        action,
+        Node {
+            width: Val::Px(32.0),
+            height: Val::Px(32.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(DARK_SLATE_GRAY.into()),
+        children![Text::new(label)],
+    )
+}
+
+fn step(value: f32, delta: f32, min: f32, max: f32) -> f32 {
+    // rounded so repeated steps don't drift
+    ((value + delta) * 100.0)
+        .round()
+        .clamp(min * 100.0, max * 100.0)
+        / 100.0
+}
+
+fn close_on_escape(keys: Res<ButtonInput<KeyCode>>, mut screen: ResMut<NextState<SettingsScreen>>) {
+    if keys.just_pressed(KeyCode::Escape) {
+        screen.set(SettingsScreen::Closed);
+    }
+}
+
+fn handle_settings_buttons(
+    buttons: Query<(&Interaction, &SettingsButton), Changed<Interaction>>,
+    mut settings: ResMut<Settings>,
+    mut screen: ResMut<NextState<SettingsScreen>>,
+) {
+    for (interaction, button) in &buttons {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        let (field, sign) = match button {
+            SettingsButton::Decrease(field) => (field, -1.0),
+            SettingsButton::Increase(field) => (field, 1.0),
+            SettingsButton::Back => {
+                screen.set(SettingsScreen::Closed);
+                continue;
+            }
+        };
+
+        match field {
+            SettingField::Volume => {
+                settings.volume = step(settings.volume, sign * VOLUME_STEP, 0.0, 1.0);
+            }
+            SettingField::Fullscreen => settings.fullscreen = !settings.fullscreen,
+            SettingField::CardScale => {
+                settings.card_scale = step(settings.card_scale, sign * CARD_SCALE_STEP, 0.5, 2.0);
+            }
+            SettingField::AnimationSpeed => {
+                settings.animation_speed = step(
+                    settings.animation_speed,
+                    sign * ANIMATION_SPEED_STEP,
+                    0.25,
+                    4.0,
+                );
+            }
+            SettingField::Rules => {
+                settings.rules = if sign > 0.0 {
+                    next_cycle(&settings.rules)
+                } else {
+                    previous_cycle(&settings.rules)
+                };
+            }
+        }
+    }
+}
+
+fn update_setting_values(settings: Res<Settings>, mut values: Query<(&SettingField, &mut Text)>) {
+    for (field, mut text) in &mut values {
+        text.0 = match field {
+            SettingField::Volume => format!("{}%", (settings.volume * 100.0).round()),
+            SettingField::Fullscreen => if settings.fullscreen { "On" } else { "Off" }.to_string(),
+            SettingField::CardScale => format!("{:.1}x", settings.card_scale),
+            SettingField::AnimationSpeed => format!("{:.2}x", settings.animation_speed),
+            SettingField::Rules => settings.rules.label().to_string(),
+        };
+    }
+}
+
+fn apply_settings(
+    settings: Res<Settings>,
+    mut volume: ResMut<GlobalVolume>,
+    mut time: ResMut<Time<Virtual>>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+    mut projections: Query<&mut Projection, With<Camera2d>>,
+) {
+    volume.volume = Volume::Linear(settings.volume);
+    time.set_relative_speed(settings.animation_speed);
+
+    let mode = if settings.fullscreen {
+        WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+    } else {
+        WindowMode::Windowed
+    };
+    for mut window in &mut windows {
+        if window.mode != mode {
+            window.mode = mode;
+        }
+    }
+
+    // cards are scaled by zooming the camera so drag and snap distances stay the same
+    for mut projection in &mut projections {
+        if let Projection::Orthographic(ortho) = projection.as_mut() {
+            ortho.scale = 1.0 / settings.card_scale;
+        }
+    }
+}
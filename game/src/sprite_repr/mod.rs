@@ -4,6 +4,8 @@ use bevy::prelude::*;
 
 use crate::{assets::AssetHandles, card::Card, card_slot::CardSlotSprite};
 
+pub const CARD_SIZE: Vec2 = Vec2::new(145.0, 200.0);
+
 trait SpriteRepr: Component + Sized {
     fn to_sprite(&self, assets: &AssetHandles) -> Sprite;
 
@@ -1,6 +1,13 @@
 use std::marker::PhantomData;
 
-use bevy::prelude::*;
+use bevy::{
+    color::palettes::css::{BLACK, GRAY, LIGHT_GREEN, LIME, RED, WHITE, YELLOW},
+    math::curve::{Curve, EaseFunction, EasingCurve},
+    prelude::*,
+    sprite::Anchor,
+};
+
+use crate::validation::ReportConfigError;
 
 pub trait GetValue {
     fn get(&self) -> f32;
@@ -15,9 +22,11 @@ pub trait IntoStatusBar: Send + Sync + 'static {
 #[relationship_target(relationship=StatusBarOf)]
 pub struct StatusBar(Vec<Entity>);
 
+/// the bar itself is either a world space [`Sprite`] or a ui [`Node`], a ui bar fills its
+/// parent node
 #[derive(Component)]
 #[relationship(relationship_target=StatusBar)]
-#[require(StatusBarDir, Sprite = enforce_exists!(Sprite))]
+#[require(StatusBarDir)]
 pub struct StatusBarOf(pub Entity);
 
 #[derive(Component, Default)]
@@ -28,10 +37,243 @@ pub enum StatusBarDir {
     Vertical,
 }
 
+#[derive(Clone, Copy, Default)]
+pub enum LabelFormat {
+    /// "12/20"
+    #[default]
+    Fraction,
+    /// "60%"
+    Percent,
+    /// "12"
+    Value,
+}
+
+impl LabelFormat {
+    fn format(&self, val: f32, max_val: f32) -> String {
+        match self {
+            LabelFormat::Fraction => format!("{}/{}", val.round(), max_val.round()),
+            LabelFormat::Percent => format!("{}%", (frac(val, max_val) * 100.0).round()),
+            LabelFormat::Value => format!("{}", val.round()),
+        }
+    }
+}
+
+/// adds a text label showing the value of the bar
+#[derive(Component, Default)]
+#[component(immutable)]
+pub struct StatusBarLabel(pub LabelFormat);
+
+#[derive(Component)]
+struct LabelText(Entity);
+
 #[derive(Component)]
 #[component(immutable)]
 struct ContainerSize(Vec2);
 
+/// how a bar moves to a new value, the trail shows the recently lost or gained amount
+#[derive(Clone, Copy)]
+pub struct StatusBarAnimation {
+    /// seconds, `0.0` changes the bar instantly
+    pub duration: f32,
+    pub easing: EaseFunction,
+    /// seconds the lagging part waits before catching up
+    pub trail_delay: f32,
+    pub loss_color: Color,
+    pub gain_color: Color,
+}
+
+impl Default for StatusBarAnimation {
+    fn default() -> Self {
+        Self {
+            duration: 0.25,
+            easing: EaseFunction::CubicOut,
+            trail_delay: 0.4,
+            loss_color: WHITE.into(),
+            gain_color: LIGHT_GREEN.into(),
+        }
+    }
+}
+
+#[derive(Resource)]
+struct BarAnimation<T: IntoStatusBar>(StatusBarAnimation, PhantomData<T>);
+
+#[derive(Clone, Copy)]
+struct Tween {
+    from: f32,
+    to: f32,
+    /// starts negative to delay the tween
+    elapsed: f32,
+}
+
+impl Tween {
+    fn instant(value: f32) -> Self {
+        Self {
+            from: value,
+            to: value,
+            elapsed: 0.0,
+        }
+    }
+
+    fn value(&self, anim: &StatusBarAnimation) -> f32 {
+        if anim.duration <= 0.0 {
+            return self.to;
+        }
+        let t = (self.elapsed / anim.duration).clamp(0.0, 1.0);
+        let eased = EasingCurve::new(0.0, 1.0, anim.easing).sample_clamped(t);
+        self.from + (self.to - self.from) * eased
+    }
+
+    fn is_done(&self, anim: &StatusBarAnimation) -> bool {
+        anim.duration <= 0.0 || self.elapsed >= anim.duration
+    }
+}
+
+#[derive(Component)]
+struct BarTween {
+    anim: StatusBarAnimation,
+    bar: Tween,
+    trail: Tween,
+    trail_color: Color,
+}
+
+impl BarTween {
+    fn new(anim: StatusBarAnimation, frac: f32) -> Self {
+        Self {
+            anim,
+            bar: Tween::instant(frac),
+            trail: Tween::instant(frac),
+            trail_color: anim.loss_color,
+        }
+    }
+
+    fn retarget(&mut self, to: f32) {
+        let bar = self.bar.value(&self.anim);
+        let trail = self.trail.value(&self.anim);
+
+        if to < bar {
+            // the bar drops right away, the trail lingers at the old value
+            self.bar = Tween {
+                from: bar,
+                to,
+                elapsed: 0.0,
+            };
+            self.trail = Tween {
+                from: trail.max(bar),
+                to,
+                elapsed: -self.anim.trail_delay,
+            };
+            self.trail_color = self.anim.loss_color;
+        } else {
+            // the trail jumps ahead, the bar follows
+            self.trail = Tween::instant(to);
+            self.bar = Tween {
+                from: bar,
+                to,
+                elapsed: -self.anim.trail_delay,
+            };
+            self.trail_color = self.anim.gain_color;
+        }
+    }
+
+    fn tick(&mut self, dt: f32) {
+        self.bar.elapsed += dt;
+        self.trail.elapsed += dt;
+    }
+
+    fn is_done(&self) -> bool {
+        self.bar.is_done(&self.anim) && self.trail.is_done(&self.anim)
+    }
+}
+
+#[derive(Component)]
+struct BarTrail(Entity);
+
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum Segments {
+    /// one continuous bar
+    #[default]
+    None,
+    /// one pip per point of the max value
+    PerPoint,
+    Count(u32),
+}
+
+/// how a bar is drawn, the background and border are spawned along with the bar
+#[derive(Component, Clone)]
+#[component(immutable)]
+pub struct StatusBarStyle {
+    pub segments: Segments,
+    /// colors from empty to full, blended by how full the bar is, empty keeps the bar's own color
+    pub gradient: Vec<Color>,
+    /// the bar pulses while its fraction is below this, `0.0` never pulses
+    pub low_threshold: f32,
+    pub background: Option<Color>,
+    /// width and color
+    pub border: Option<(f32, Color)>,
+}
+
+impl Default for StatusBarStyle {
+    fn default() -> Self {
+        Self {
+            segments: Segments::None,
+            gradient: vec![],
+            low_threshold: 0.0,
+            background: Some(GRAY.into()),
+            border: None,
+        }
+    }
+}
+
+impl StatusBarStyle {
+    /// red to yellow to green, pulsing below a quarter
+    pub fn health() -> Self {
+        Self {
+            gradient: vec![RED.into(), YELLOW.into(), LIME.into()],
+            low_threshold: 0.25,
+            border: Some((2.0, BLACK.into())),
+            ..default()
+        }
+    }
+
+    pub fn with_segments(mut self, segments: Segments) -> Self {
+        self.segments = segments;
+        self
+    }
+
+    fn color(&self, base: Color, frac: f32) -> Color {
+        let Some(last) = self.gradient.len().checked_sub(1) else {
+            return base;
+        };
+        let pos = frac.clamp(0.0, 1.0) * last as f32;
+        let idx = (pos.floor() as usize).min(last.saturating_sub(1));
+        let next = (idx + 1).min(last);
+        self.gradient[idx].mix(&self.gradient[next], pos - idx as f32)
+    }
+
+    fn segment_count(&self, max_val: f32) -> u32 {
+        match self.segments {
+            Segments::None => 0,
+            Segments::PerPoint => max_val.round().max(0.0) as u32,
+            Segments::Count(count) => count,
+        }
+    }
+}
+
+/// the color the bar was spawned with, used when its style has no gradient
+#[derive(Component)]
+struct BaseColor(Color);
+
+/// background and border drawn behind a styled bar
+#[derive(Component)]
+struct BarFrame {
+    background: Option<Entity>,
+    border: Option<Entity>,
+}
+
+/// separators between the pips of a segmented bar
+#[derive(Component, Default)]
+struct SegmentDividers(Vec<Entity>);
+
 #[derive(Component)]
 #[require(StatusBarOf = enforce_exists!(StatusBarOf))]
 #[component(immutable)]
@@ -43,95 +285,593 @@ impl<T: IntoStatusBar> Default for StatusBarType<T> {
     }
 }
 
-pub struct StatusBarPlugin<T: IntoStatusBar>(PhantomData<T>);
+pub struct StatusBarPlugin<T: IntoStatusBar> {
+    animation: StatusBarAnimation,
+    _marker: PhantomData<T>,
+}
 
 impl<T: IntoStatusBar> Default for StatusBarPlugin<T> {
     fn default() -> Self {
-        Self(Default::default())
+        Self {
+            animation: Default::default(),
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<T: IntoStatusBar> StatusBarPlugin<T> {
+    pub fn with_animation(mut self, animation: StatusBarAnimation) -> Self {
+        self.animation = animation;
+        self
     }
 }
 
 impl<T: IntoStatusBar> Plugin for StatusBarPlugin<T> {
     fn build(&self, app: &mut App) {
-        app.add_observer(handle_new::<T>)
+        app.insert_resource(BarAnimation::<T>(self.animation, PhantomData))
+            .add_observer(handle_new::<T>)
             .add_observer(handle_change::<T>)
-            .add_observer(handle_remove::<T>);
+            .add_observer(handle_remove::<T>)
+            .add_systems(
+                Update,
+                (
+                    resolve_container_size::<T>,
+                    animate::<T>,
+                    size_frames::<T>,
+                    update_segments::<T>,
+                    style_bars::<T>,
+                )
+                    .chain(),
+            );
+    }
+}
+
+fn frac(val: f32, max_val: f32) -> f32 {
+    if max_val > 0.0 {
+        (val / max_val).clamp(0.0, 1.0)
+    } else {
+        0.0
+    }
+}
+
+fn progress_size(size: Vec2, dir: &StatusBarDir, frac: f32) -> Vec2 {
+    match dir {
+        StatusBarDir::Horizontal => size.with_x(size.x * frac),
+        StatusBarDir::Vertical => size.with_y(size.y * frac),
+    }
+}
+
+/// sets the length of a sprite or node bar
+fn apply_progress(
+    sprite: Option<Mut<Sprite>>,
+    node: Option<Mut<Node>>,
+    size: Option<&ContainerSize>,
+    dir: &StatusBarDir,
+    frac: f32,
+) {
+    // only written on change so the bar isn't re-laid out or re-rendered for nothing
+    if let Some(mut node) = node {
+        let len = Val::Percent(frac * 100.0);
+        match dir {
+            StatusBarDir::Horizontal if node.width != len => node.width = len,
+            StatusBarDir::Vertical if node.height != len => node.height = len,
+            _ => {}
+        }
+    } else if let (Some(mut sprite), Some(size)) = (sprite, size) {
+        let new = Some(progress_size(size.0, dir, frac));
+        if sprite.custom_size != new {
+            sprite.custom_size = new;
+        }
+    }
+}
+
+/// the size of a sprite bar when full, from its custom size or else its image
+fn container_size(sprite: &Sprite, images: &Assets<Image>) -> Option<Vec2> {
+    sprite
+        .custom_size
+        .or_else(|| sprite.rect.map(|r| r.size()))
+        .or_else(|| images.get(&sprite.image).map(|i| i.size_f32()))
+}
+
+/// horizontal bars are labeled in their center, vertical ones above
+fn label_position(size: Vec2, anchor: &Anchor, dir: &StatusBarDir) -> Vec3 {
+    let center = -anchor.as_vec() * size;
+    match dir {
+        StatusBarDir::Horizontal => center.extend(1.0),
+        StatusBarDir::Vertical => (center + Vec2::new(0.0, size.y / 2.0 + 12.0)).extend(1.0),
+    }
+}
+
+fn set_label(
+    label: Option<(&StatusBarLabel, &LabelText)>,
+    texts: &mut Query<(Option<&mut Text>, Option<&mut Text2d>)>,
+    val: f32,
+    max_val: f32,
+) {
+    let Some((format, text_e)) = label else {
+        return;
+    };
+    let text = format.0.format(val, max_val);
+    match texts.get_mut(text_e.0) {
+        Ok((Some(mut ui_text), _)) => ui_text.0 = text,
+        Ok((_, Some(mut text_2d))) => text_2d.0 = text,
+        _ => {}
+    }
+}
+
+/// ui bars are absolutely positioned inside their parent so the trail can sit behind them
+fn fill_parent(node: &mut Node, dir: &StatusBarDir, frac: f32) {
+    node.position_type = PositionType::Absolute;
+    node.left = Val::Px(0.0);
+    node.bottom = Val::Px(0.0);
+    match dir {
+        StatusBarDir::Horizontal => {
+            node.width = Val::Percent(frac * 100.0);
+            node.height = Val::Percent(100.0);
+        }
+        StatusBarDir::Vertical => {
+            node.width = Val::Percent(100.0);
+            node.height = Val::Percent(frac * 100.0);
+        }
     }
 }
 
 #[allow(clippy::type_complexity)]
 fn handle_new<T: IntoStatusBar>(
-    tr: Trigger<OnInsert, StatusBarOf>,
-    mut status_bars: Query<(&mut Sprite, &StatusBarOf, &StatusBarDir), With<StatusBarType<T>>>,
+    tr: Trigger<OnInsert, StatusBarType<T>>,
+    mut status_bars: Query<
+        (
+            &StatusBarOf,
+            &StatusBarDir,
+            Option<&mut Sprite>,
+            Option<&mut Node>,
+            Option<&StatusBarLabel>,
+            Option<&StatusBarStyle>,
+            Option<&BackgroundColor>,
+            Option<&ChildOf>,
+        ),
+        With<StatusBarType<T>>,
+    >,
     targets: Query<(&T::GetMaxValue, &T::GetValue)>,
+    images: Res<Assets<Image>>,
+    anim: Res<BarAnimation<T>>,
     mut commands: Commands,
 ) {
     let e = tr.target();
-    let (mut sprite, target, dir) = status_bars.get_mut(e).unwrap();
-    let (max_val, val) = targets.get(target.0).unwrap();
+    let Ok((target, dir, sprite, node, label, style, bg, parent)) = status_bars.get_mut(e) else {
+        return;
+    };
+    let Ok((max_val, val)) = targets.get(target.0) else {
+        commands.trigger(ReportConfigError {
+            entity: e,
+            message: format!(
+                "status bar target is missing {}",
+                std::any::type_name::<T::GetValue>()
+            ),
+        });
+        return;
+    };
+    let frac = frac(val.get(), max_val.get());
+    let label_text = label.map(|l| l.0.format(val.get(), max_val.get()));
 
-    let size = sprite
-        .custom_size
-        .expect("Sprite should have customs size!"); // TODO retrieve actual size
-    commands.entity(e).insert(ContainerSize(size));
+    let (trail, label, frame, base) = match (sprite, node, parent) {
+        (Some(mut sprite), _, _) => {
+            let size = container_size(&sprite, &images);
+            let mut frame_sprite = |color: Color, z: f32| {
+                commands
+                    .spawn((
+                        ChildOf(e),
+                        Sprite {
+                            color,
+                            custom_size: size,
+                            anchor: sprite.anchor,
+                            ..default()
+                        },
+                        Transform::from_xyz(0.0, 0.0, z),
+                    ))
+                    .id()
+            };
+            let frame = BarFrame {
+                background: style
+                    .and_then(|s| s.background)
+                    .map(|c| frame_sprite(c, -0.75)),
+                border: style
+                    .and_then(|s| s.border)
+                    .map(|(_, c)| frame_sprite(c, -0.9)),
+            };
 
-    let frac = val.get() / max_val.get();
-    let progress_size = match dir {
-        StatusBarDir::Horizontal => size.with_x(size.x * frac),
-        StatusBarDir::Vertical => size.with_y(size.y * frac),
+            let trail = commands
+                .spawn((
+                    ChildOf(e),
+                    Sprite {
+                        color: anim.0.loss_color,
+                        custom_size: size.map(|s| progress_size(s, dir, frac)),
+                        anchor: sprite.anchor,
+                        ..default()
+                    },
+                    Transform::from_xyz(0.0, 0.0, -0.5),
+                ))
+                .id();
+
+            let label = label_text.map(|text| {
+                let pos = size.map_or(Vec3::Z, |s| label_position(s, &sprite.anchor, dir));
+                let font_size = size.map_or(24.0, |s| s.y.min(24.0));
+                commands
+                    .spawn((
+                        ChildOf(e),
+                        Text2d::new(text),
+                        TextFont::from_font_size(font_size),
+                        Transform::from_translation(pos),
+                    ))
+                    .id()
+            });
+
+            // the image may still be loading, the size is resolved later in that case
+            if let Some(size) = size {
+                commands.entity(e).insert(ContainerSize(size));
+                sprite.custom_size = Some(progress_size(size, dir, frac));
+            }
+
+            (trail, label, frame, sprite.color)
+        }
+        (None, Some(mut node), Some(parent)) => {
+            fill_parent(&mut node, dir, frac);
+
+            let background = style
+                .filter(|s| s.background.is_some() || s.border.is_some())
+                .map(|style| {
+                    let (width, border) = style.border.unwrap_or((0.0, Color::NONE));
+                    let mut frame_node = Node {
+                        border: UiRect::all(Val::Px(width)),
+                        ..default()
+                    };
+                    fill_parent(&mut frame_node, dir, 1.0);
+                    commands
+                        .spawn((
+                            ChildOf(parent.parent()),
+                            frame_node,
+                            BackgroundColor(style.background.unwrap_or(Color::NONE)),
+                            BorderColor(border),
+                            ZIndex(-2),
+                        ))
+                        .id()
+                });
+            let frame = BarFrame {
+                background,
+                border: None,
+            };
+
+            let mut trail_node = node.clone();
+            fill_parent(&mut trail_node, dir, frac);
+            let trail = commands
+                .spawn((
+                    ChildOf(parent.parent()),
+                    trail_node,
+                    BackgroundColor(anim.0.loss_color),
+                    ZIndex(-1),
+                ))
+                .id();
+
+            let label = label_text.map(|text| {
+                let text = commands
+                    .spawn((Text::new(text), TextFont::from_font_size(16.0)))
+                    .id();
+                commands
+                    .spawn((
+                        ChildOf(parent.parent()),
+                        Node {
+                            position_type: PositionType::Absolute,
+                            width: Val::Percent(100.0),
+                            height: Val::Percent(100.0),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        ZIndex(1),
+                    ))
+                    .add_child(text);
+                text
+            });
+
+            (trail, label, frame, bg.map_or(Color::WHITE, |bg| bg.0))
+        }
+        _ => {
+            commands.trigger(ReportConfigError {
+                entity: e,
+                message: "status bar needs a Sprite or a Node with a parent".to_string(),
+            });
+            return;
+        }
     };
 
-    sprite.custom_size = Some(progress_size);
+    commands
+        .entity(e)
+        .insert((BarTween::new(anim.0, frac), BarTrail(trail)));
+    if let Some(label) = label {
+        commands.entity(e).insert(LabelText(label));
+    }
+    if let Some(style) = style {
+        commands.entity(e).insert((frame, BaseColor(base)));
+        if style.segments != Segments::None {
+            commands.entity(e).insert(SegmentDividers::default());
+        }
+    }
 }
 
+#[allow(clippy::type_complexity)]
 fn handle_change<T: IntoStatusBar>(
     tr: Trigger<OnInsert, (T::GetValue, T::GetMaxValue)>,
     targets: Query<(&T::GetMaxValue, &T::GetValue, &StatusBar)>,
-    mut status_bars: Query<(&mut Sprite, &ContainerSize, &StatusBarDir)>,
+    mut status_bars: Query<
+        (&mut BarTween, Option<(&StatusBarLabel, &LabelText)>),
+        With<StatusBarType<T>>,
+    >,
+    mut texts: Query<(Option<&mut Text>, Option<&mut Text2d>)>,
 ) {
     let target = tr.target();
     let Ok((max_val, val, bar_ref)) = targets.get(target) else {
         return;
     };
 
-    let Some(bar_entity) = bar_ref.0.iter().find(|bar| status_bars.contains(**bar)) else {
+    let mut bars = status_bars.iter_many_mut(bar_ref.iter());
+    while let Some((mut tween, label)) = bars.fetch_next() {
+        tween.retarget(frac(val.get(), max_val.get()));
+        set_label(label, &mut texts, val.get(), max_val.get());
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn handle_remove<T: IntoStatusBar>(
+    tr: Trigger<OnRemove, T::GetValue>,
+    targets: Query<(&StatusBar, Option<&T::GetMaxValue>)>,
+    mut status_bars: Query<
+        (&mut BarTween, Option<(&StatusBarLabel, &LabelText)>),
+        With<StatusBarType<T>>,
+    >,
+    mut texts: Query<(Option<&mut Text>, Option<&mut Text2d>)>,
+) {
+    let target = tr.target();
+    let Ok((bar_ref, max_val)) = targets.get(target) else {
         return;
     };
 
-    let (mut sprite, size, dir) = status_bars.get_mut(*bar_entity).unwrap();
+    let mut bars = status_bars.iter_many_mut(bar_ref.iter());
+    while let Some((mut tween, label)) = bars.fetch_next() {
+        tween.retarget(0.0);
+        set_label(label, &mut texts, 0.0, max_val.map_or(0.0, |m| m.get()));
+    }
+}
+
+/// sprite bars without a custom size wait for their image to be loaded
+#[allow(clippy::type_complexity)]
+fn resolve_container_size<T: IntoStatusBar>(
+    status_bars: Query<
+        (Entity, &Sprite, &StatusBarDir, Option<&LabelText>),
+        (
+            With<StatusBarType<T>>,
+            With<BarTween>,
+            Without<ContainerSize>,
+        ),
+    >,
+    mut labels: Query<(&mut Transform, &mut TextFont)>,
+    images: Res<Assets<Image>>,
+    mut commands: Commands,
+) {
+    for (e, sprite, dir, label) in &status_bars {
+        let Some(size) = container_size(sprite, &images) else {
+            continue;
+        };
+        commands.entity(e).insert(ContainerSize(size));
+
+        if let Some((mut tf, mut font)) = label.and_then(|l| labels.get_mut(l.0).ok()) {
+            tf.translation = label_position(size, &sprite.anchor, dir);
+            font.font_size = size.y.min(24.0);
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn animate<T: IntoStatusBar>(
+    mut status_bars: Query<
+        (
+            &mut BarTween,
+            Option<&mut Sprite>,
+            Option<&mut Node>,
+            Option<Ref<ContainerSize>>,
+            &StatusBarDir,
+            &BarTrail,
+        ),
+        With<StatusBarType<T>>,
+    >,
+    mut trails: Query<
+        (
+            Option<&mut Sprite>,
+            Option<&mut Node>,
+            Option<&mut BackgroundColor>,
+        ),
+        Without<BarTween>,
+    >,
+    time: Res<Time>,
+) {
+    for (mut tween, sprite, node, size, dir, trail) in &mut status_bars {
+        // the final value was written on the last tick, unless retargeted or resized since
+        let resized = size.as_ref().is_some_and(|s| s.is_changed());
+        if tween.is_done() && !tween.is_changed() && !resized {
+            continue;
+        }
+        tween.tick(time.delta_secs());
 
-    let size = size.0;
+        let size = size.as_deref();
+        let anim = tween.anim;
+        apply_progress(sprite, node, size, dir, tween.bar.value(&anim));
 
-    let frac = val.get() / max_val.get();
-    let progress_size = match dir {
-        StatusBarDir::Horizontal => size.with_x(size.x * frac),
-        StatusBarDir::Vertical => size.with_y(size.y * frac),
-    };
+        let Ok((mut trail_sprite, trail_node, trail_bg)) = trails.get_mut(trail.0) else {
+            continue;
+        };
+        if let Some(sprite) = trail_sprite
+            .as_mut()
+            .filter(|s| s.color != tween.trail_color)
+        {
+            sprite.color = tween.trail_color;
+        }
+        if let Some(mut bg) = trail_bg {
+            bg.set_if_neq(BackgroundColor(tween.trail_color));
+        }
+        apply_progress(
+            trail_sprite,
+            trail_node,
+            size,
+            dir,
+            tween.trail.value(&anim),
+        );
+    }
+}
 
-    sprite.custom_size = Some(progress_size);
+/// keeps the background and border of sprite bars as large as the bar when full
+#[allow(clippy::type_complexity)]
+fn size_frames<T: IntoStatusBar>(
+    status_bars: Query<
+        (&ContainerSize, &BarFrame, &StatusBarStyle, &Sprite),
+        (With<StatusBarType<T>>, Changed<ContainerSize>),
+    >,
+    mut frames: Query<(&mut Sprite, &mut Transform), Without<BarTween>>,
+) {
+    for (size, frame, style, bar_sprite) in &status_bars {
+        if let Some(Ok((mut sprite, _))) = frame.background.map(|e| frames.get_mut(e)) {
+            sprite.custom_size = Some(size.0);
+        }
+        if let (Some(Ok((mut sprite, mut tf))), Some((width, _))) =
+            (frame.border.map(|e| frames.get_mut(e)), style.border)
+        {
+            // grown around the bar regardless of its anchor
+            sprite.custom_size = Some(size.0 + 2.0 * width);
+            let offset = bar_sprite.anchor.as_vec() * 2.0 * width;
+            tf.translation = offset.extend(tf.translation.z);
+        }
+    }
 }
 
-fn handle_remove<T: IntoStatusBar>(
-    tr: Trigger<OnRemove, T::GetValue>,
-    targets: Query<&StatusBar>,
-    mut status_bars: Query<(&mut Sprite, &ContainerSize, &StatusBarDir)>,
+/// respawns the dividers of segmented bars whenever the segment count or bar size changes
+#[allow(clippy::type_complexity)]
+fn update_segments<T: IntoStatusBar>(
+    mut status_bars: Query<
+        (
+            Entity,
+            &StatusBarOf,
+            &StatusBarStyle,
+            &StatusBarDir,
+            &mut SegmentDividers,
+            Option<Ref<ContainerSize>>,
+            Option<&Sprite>,
+            Option<&ChildOf>,
+            Has<Node>,
+        ),
+        With<StatusBarType<T>>,
+    >,
+    targets: Query<&T::GetMaxValue>,
+    mut commands: Commands,
 ) {
-    let target = tr.target();
-    let Ok(bar_ref) = targets.get(target) else {
-        return;
-    };
+    for (e, target, style, dir, mut dividers, size, sprite, parent, is_node) in &mut status_bars {
+        let Ok(max_val) = targets.get(target.0) else {
+            continue;
+        };
+        let count = style.segment_count(max_val.get());
+        let wanted = count.saturating_sub(1) as usize;
+        let resized = size.as_ref().is_some_and(|s| s.is_changed());
+        if dividers.0.len() == wanted && !resized {
+            continue;
+        }
 
-    let Some(bar_entity) = bar_ref.0.iter().find(|bar| status_bars.contains(**bar)) else {
-        return;
-    };
+        for divider in dividers.0.drain(..) {
+            commands.entity(divider).despawn();
+        }
 
-    let (mut sprite, size, dir) = status_bars.get_mut(*bar_entity).unwrap();
-    let size = size.0;
+        let color = style.background.unwrap_or(BLACK.into());
+        for i in 1..count {
+            let at = i as f32 / count as f32;
+            let divider = match (sprite, size.as_ref(), parent) {
+                (Some(sprite), Some(size), _) if !is_node => {
+                    let start = (-Vec2::splat(0.5) - sprite.anchor.as_vec()) * size.0;
+                    let (pos, divider_size) = match dir {
+                        StatusBarDir::Horizontal => (
+                            Vec2::new(start.x + at * size.0.x, start.y + size.0.y / 2.0),
+                            Vec2::new(2.0, size.0.y),
+                        ),
+                        StatusBarDir::Vertical => (
+                            Vec2::new(start.x + size.0.x / 2.0, start.y + at * size.0.y),
+                            Vec2::new(size.0.x, 2.0),
+                        ),
+                    };
+                    commands
+                        .spawn((
+                            ChildOf(e),
+                            Sprite::from_color(color, divider_size),
+                            Transform::from_translation(pos.extend(0.6)),
+                        ))
+                        .id()
+                }
+                (_, _, Some(parent)) if is_node => {
+                    let node = match dir {
+                        StatusBarDir::Horizontal => Node {
+                            left: Val::Percent(at * 100.0),
+                            width: Val::Px(2.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        StatusBarDir::Vertical => Node {
+                            bottom: Val::Percent(at * 100.0),
+                            width: Val::Percent(100.0),
+                            height: Val::Px(2.0),
+                            ..default()
+                        },
+                    };
+                    commands
+                        .spawn((
+                            ChildOf(parent.parent()),
+                            Node {
+                                position_type: PositionType::Absolute,
+                                ..node
+                            },
+                            BackgroundColor(color),
+                            ZIndex(1),
+                        ))
+                        .id()
+                }
+                // the sprite size isn't known yet
+                _ => break,
+            };
+            dividers.0.push(divider);
+        }
+    }
+}
 
-    let progress_size = match dir {
-        StatusBarDir::Horizontal => size.with_x(0.0),
-        StatusBarDir::Vertical => size.with_y(0.0),
-    };
+/// colors styled bars by their fraction and pulses them while low
+#[allow(clippy::type_complexity)]
+fn style_bars<T: IntoStatusBar>(
+    mut status_bars: Query<
+        (
+            &BarTween,
+            &StatusBarStyle,
+            &BaseColor,
+            Option<&mut Sprite>,
+            Option<&mut BackgroundColor>,
+        ),
+        With<StatusBarType<T>>,
+    >,
+    time: Res<Time>,
+) {
+    for (tween, style, base, sprite, bg) in &mut status_bars {
+        let frac = tween.bar.value(&tween.anim);
+        let mut color = style.color(base.0, frac);
+        if frac < style.low_threshold {
+            let pulse = (time.elapsed_secs() * 6.0).sin().abs();
+            color = color.with_alpha(color.alpha() * (0.4 + 0.6 * pulse));
+        }
 
-    sprite.custom_size = Some(progress_size);
+        if let Some(mut sprite) = sprite {
+            sprite.color = color;
+        } else if let Some(mut bg) = bg {
+            bg.0 = color;
+        }
+    }
 }
@@ -0,0 +1,162 @@
+use bevy::prelude::*;
+
+use crate::{
+    status_bar::{GetValue, IntoStatusBar, StatusBarOf, StatusBarPlugin, StatusBarType},
+    validation::ReportConfigError,
+};
+
+type ReadValue = Box<dyn Fn(&World) -> Option<f32> + Send + Sync>;
+
+/// binds a bar to any value in the world without a dedicated [`IntoStatusBar`] type, the bar
+/// only needs this and a `Sprite` or `Node`, the max defaults to zero
+#[derive(Component)]
+pub struct StatusBarSource {
+    value: ReadValue,
+    max: ReadValue,
+}
+
+impl StatusBarSource {
+    /// reads the value from a component of `target`, a missing component reads as zero
+    pub fn component<C: Component>(
+        target: Entity,
+        read: impl Fn(&C) -> f32 + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            value: Box::new(move |world| world.get::<C>(target).map(&read)),
+            max: Box::new(|_| None),
+        }
+    }
+
+    pub fn resource<R: Resource>(read: impl Fn(&R) -> f32 + Send + Sync + 'static) -> Self {
+        Self {
+            value: Box::new(move |world| world.get_resource::<R>().map(&read)),
+            max: Box::new(|_| None),
+        }
+    }
+
+    pub fn with_max(mut self, max: f32) -> Self {
+        self.max = Box::new(move |_| Some(max));
+        self
+    }
+
+    pub fn with_max_component<C: Component>(
+        mut self,
+        target: Entity,
+        read: impl Fn(&C) -> f32 + Send + Sync + 'static,
+    ) -> Self {
+        self.max = Box::new(move |world| world.get::<C>(target).map(&read));
+        self
+    }
+
+    pub fn with_max_resource<R: Resource>(
+        mut self,
+        read: impl Fn(&R) -> f32 + Send + Sync + 'static,
+    ) -> Self {
+        self.max = Box::new(move |world| world.get_resource::<R>().map(&read));
+        self
+    }
+}
+
+/// the [`IntoStatusBar`] driving every [`StatusBarSource`] bar
+pub struct SourcedBar;
+
+/// the last read value of a [`StatusBarSource`], kept on a hidden child of the bar
+#[derive(Component, PartialEq)]
+#[component(immutable)]
+pub struct SourcedValue(f32);
+
+#[derive(Component, PartialEq)]
+#[component(immutable)]
+pub struct SourcedMax(f32);
+
+/// the hidden entity spawned by [`read_sources`] for a bar, only such a [`StatusBarOf`] target is
+/// reused as the proxy of the bar
+#[derive(Component)]
+#[relationship(relationship_target=SourceProxy)]
+pub struct SourceProxyOf(pub Entity);
+
+/// despawns the proxy along with its bar
+#[derive(Component)]
+#[relationship_target(relationship=SourceProxyOf, linked_spawn)]
+pub struct SourceProxy(Entity);
+
+impl GetValue for SourcedValue {
+    fn get(&self) -> f32 {
+        self.0
+    }
+}
+
+impl GetValue for SourcedMax {
+    fn get(&self) -> f32 {
+        self.0
+    }
+}
+
+impl IntoStatusBar for SourcedBar {
+    type GetValue = SourcedValue;
+    type GetMaxValue = SourcedMax;
+}
+
+pub struct StatusBarSourcePlugin;
+
+impl Plugin for StatusBarSourcePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(StatusBarPlugin::<SourcedBar>::default())
+            .add_systems(PreUpdate, read_sources);
+    }
+}
+
+/// copies source values onto the bars' proxies, only writing changes so bars only animate then
+fn read_sources(world: &mut World) {
+    let mut bars = world.query::<(Entity, &StatusBarSource, Option<&StatusBarOf>)>();
+    let reads: Vec<_> = bars
+        .iter(world)
+        .map(|(bar, source, proxy)| {
+            let value = (source.value)(world).unwrap_or(0.0);
+            let max = (source.max)(world).unwrap_or(0.0);
+            (
+                bar,
+                proxy.map(|p| p.0),
+                SourcedValue(value),
+                SourcedMax(max),
+            )
+        })
+        .collect();
+
+    for (bar, target, value, max) in reads {
+        let proxy = world.get::<SourceProxy>(bar).map(|p| p.0);
+        let Some(proxy) = proxy else {
+            // a bar of another type, the source can't drive it
+            if let Some(target) = target {
+                world.trigger(ReportConfigError {
+                    entity: bar,
+                    message: format!("status bar source on a bar already showing {}", target),
+                });
+                continue;
+            }
+
+            let proxy = world
+                .spawn((
+                    Name::new("Status Bar Source"),
+                    SourceProxyOf(bar),
+                    value,
+                    max,
+                ))
+                .id();
+            world
+                .entity_mut(bar)
+                .insert((StatusBarOf(proxy), StatusBarType::<SourcedBar>::default()));
+            continue;
+        };
+
+        let Ok(mut proxy) = world.get_entity_mut(proxy) else {
+            continue;
+        };
+        if proxy.get::<SourcedMax>() != Some(&max) {
+            proxy.insert(max);
+        }
+        if proxy.get::<SourcedValue>() != Some(&value) {
+            proxy.insert(value);
+        }
+    }
+}
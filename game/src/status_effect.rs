@@ -0,0 +1,184 @@
+use bevy::{
+    color::palettes::css::{GOLD, HOT_PINK, LIME},
+    prelude::*,
+};
+use serde::Deserialize;
+
+use crate::health::{Damage, DamageKind, Heal, Health};
+
+const ICON_SIZE: f32 = 30.0;
+const ICON_SPACING: f32 = 36.0;
+
+/// game time, effects last a number of cards played or rooms entered instead of seconds
+#[derive(Event, Clone, Copy, PartialEq, Eq, Debug, Deserialize)]
+pub enum GameTick {
+    CardPlayed,
+    RoomEntered,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize)]
+pub enum EffectKind {
+    /// deals one damage per stack every tick
+    Poison,
+    /// heals one per stack every tick
+    Regeneration,
+    /// the entity is [`Stunned`] while it lasts
+    Stun,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize)]
+pub struct StatusEffect {
+    pub kind: EffectKind,
+    pub stacks: u32,
+    /// remaining ticks
+    pub duration: u32,
+    pub tick: GameTick,
+}
+
+#[derive(Component, Default)]
+pub struct StatusEffects(Vec<StatusEffect>);
+
+impl StatusEffects {
+    pub fn get(&self, kind: EffectKind) -> Option<&StatusEffect> {
+        self.0.iter().find(|e| e.kind == kind)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &StatusEffect> {
+        self.0.iter()
+    }
+}
+
+/// adds an effect to the target, stacking with an active effect of the same kind
+#[derive(Event)]
+pub struct ApplyEffect(pub StatusEffect);
+
+#[derive(Component)]
+pub struct Stunned;
+
+/// renders the effects of the given entity as a row of icons
+#[derive(Component)]
+#[require(Transform, Visibility)]
+pub struct EffectIcons(pub Entity);
+
+pub struct StatusEffectPlugin;
+
+impl Plugin for StatusEffectPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(handle_apply_effect)
+            .add_observer(handle_tick)
+            .add_systems(Update, (update_stunned, update_effect_icons));
+    }
+}
+
+fn handle_apply_effect(
+    tr: Trigger<ApplyEffect>,
+    mut effects: Query<&mut StatusEffects>,
+    mut commands: Commands,
+) {
+    let entity = tr.target();
+    let new = tr.0;
+
+    let Ok(mut effects) = effects.get_mut(entity) else {
+        commands.entity(entity).insert(StatusEffects(vec![new]));
+        return;
+    };
+
+    match effects.0.iter_mut().find(|e| e.kind == new.kind) {
+        Some(active) => {
+            active.stacks += new.stacks;
+            active.duration = active.duration.max(new.duration);
+        }
+        None => effects.0.push(new),
+    }
+}
+
+fn handle_tick(
+    tr: Trigger<GameTick>,
+    // health is removed at zero, a dead entity would be revived by its regeneration
+    mut targets: Query<(Entity, &mut StatusEffects), With<Health>>,
+    mut commands: Commands,
+) {
+    let tick = *tr.event();
+
+    for (entity, mut effects) in &mut targets {
+        for effect in effects.0.iter_mut().filter(|e| e.tick == tick) {
+            match effect.kind {
+                EffectKind::Poison => {
+                    commands.entity(entity).trigger(Damage {
+                        amount: effect.stacks,
+                        kind: DamageKind::True,
+                        source: None,
+                    });
+                }
+                EffectKind::Regeneration => {
+                    commands.entity(entity).trigger(Heal {
+                        amount: effect.stacks,
+                        source: None,
+                    });
+                }
+                EffectKind::Stun => {}
+            }
+            effect.duration = effect.duration.saturating_sub(1);
+        }
+
+        effects.0.retain(|e| e.duration > 0);
+    }
+}
+
+fn update_stunned(
+    targets: Query<(Entity, &StatusEffects, Has<Stunned>), Changed<StatusEffects>>,
+    mut commands: Commands,
+) {
+    for (entity, effects, stunned) in &targets {
+        match (effects.get(EffectKind::Stun).is_some(), stunned) {
+            (true, false) => {
+                commands.entity(entity).insert(Stunned);
+            }
+            (false, true) => {
+                commands.entity(entity).remove::<Stunned>();
+            }
+            _ => {}
+        }
+    }
+}
+
+fn update_effect_icons(
+    containers: Query<(Entity, &EffectIcons)>,
+    targets: Query<Ref<StatusEffects>>,
+    mut commands: Commands,
+) {
+    for (container, icons) in &containers {
+        let Ok(effects) = targets.get(icons.0) else {
+            continue;
+        };
+        if !effects.is_changed() {
+            continue;
+        }
+
+        commands.entity(container).despawn_related::<Children>();
+
+        for (idx, effect) in effects.iter().enumerate() {
+            let (color, letter) = match effect.kind {
+                EffectKind::Poison => (LIME, "P"),
+                EffectKind::Regeneration => (HOT_PINK, "R"),
+                EffectKind::Stun => (GOLD, "S"),
+            };
+
+            commands.spawn((
+                ChildOf(container),
+                Sprite {
+                    color: color.into(),
+                    custom_size: Some(Vec2::splat(ICON_SIZE)),
+                    ..default()
+                },
+                Transform::from_xyz(idx as f32 * ICON_SPACING, 0.0, 0.0),
+                children![(
+                    Text2d::new(format!("{}{}", letter, effect.stacks)),
+                    TextFont::from_font_size(16.0),
+                    TextColor(Color::BLACK),
+                    Transform::from_xyz(0.0, 0.0, 0.1),
+                )],
+            ));
+        }
+    }
+}
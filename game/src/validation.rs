@@ -0,0 +1,107 @@
+use bevy::{color::palettes::css::RED, prelude::*};
+
+use crate::menu::game_running;
+
+#[derive(Reflect, Clone, PartialEq)]
+pub struct ConfigError {
+    pub entity: Entity,
+    pub message: String,
+}
+
+/// every misconfiguration reported so far, queryable over BRP via `bevy/get_resource`
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+pub struct ConfigErrors(pub Vec<ConfigError>);
+
+/// reports a misconfigured entity instead of panicking
+#[derive(Event)]
+pub struct ReportConfigError {
+    pub entity: Entity,
+    pub message: String,
+}
+
+/// shows [`ConfigErrors`] on screen, toggled with F3
+#[derive(Resource, Default)]
+pub struct ShowConfigErrors(pub bool);
+
+#[derive(Component)]
+struct ConfigErrorOverlay;
+
+pub struct ValidationPlugin;
+
+impl Plugin for ValidationPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<ConfigErrors>()
+            .init_resource::<ConfigErrors>()
+            .init_resource::<ShowConfigErrors>()
+            .add_observer(handle_report)
+            .add_systems(Startup, spawn_overlay)
+            .add_systems(
+                Update,
+                (toggle_overlay.run_if(game_running), update_overlay).chain(),
+            );
+    }
+}
+
+fn handle_report(tr: Trigger<ReportConfigError>, mut errors: ResMut<ConfigErrors>) {
+    let error = ConfigError {
+        entity: tr.entity,
+        message: tr.message.clone(),
+    };
+    if errors.0.contains(&error) {
+        return;
+    }
+
+    error!("misconfigured entity {}: {}", error.entity, error.message);
+    errors.0.push(error);
+}
+
+fn spawn_overlay(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Config Error Overlay"),
+        ConfigErrorOverlay,
+        Text::default(),
+        TextFont::from_font_size(14.0),
+        TextColor(RED.into()),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(8.0),
+            top: Val::Px(8.0),
+            ..default()
+        },
+        Visibility::Hidden,
+        GlobalZIndex(20),
+    ));
+}
+
+fn toggle_overlay(keys: Res<ButtonInput<KeyCode>>, mut show: ResMut<ShowConfigErrors>) {
+    if keys.just_pressed(KeyCode::F3) {
+        show.0 = !show.0;
+    }
+}
+
+fn update_overlay(
+    errors: Res<ConfigErrors>,
+    show: Res<ShowConfigErrors>,
+    mut overlay: Query<(&mut Text, &mut Visibility), With<ConfigErrorOverlay>>,
+) {
+    if !errors.is_changed() && !show.is_changed() {
+        return;
+    }
+    let Ok((mut text, mut visibility)) = overlay.single_mut() else {
+        return;
+    };
+
+    *visibility = if show.0 {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+
+    text.0 = errors
+        .0
+        .iter()
+        .map(|e| format!("{}: {}", e.entity, e.message))
+        .collect::<Vec<_>>()
+        .join("\n");
+}